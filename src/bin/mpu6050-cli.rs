@@ -0,0 +1,530 @@
+//! Command-line tool for bench work with an MPU6050 on Linux
+//!
+//! Talks to the device through `/dev/i2c-N`, or to an in-process simulated device
+//! with `--sim`. Run `mpu6050-cli help` for usage.
+
+use std::env;
+use std::io::{self, Write};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::{ErrorType, I2c, Operation, SevenBitAddress};
+use linux_embedded_hal::I2cdev;
+use mpu6050::config::{AccelConfig, DeviceConfig, DlpfConfig, GyroConfig};
+use mpu6050::mpu6050::Mpu6050;
+use mpu6050::sim::{SimError, SimulatedMpu6050};
+
+const USAGE: &str = "\
+Usage: mpu6050-cli [--bus /dev/i2c-N | --sim] [--address ADDR] <COMMAND> [OPTIONS]
+
+Commands:
+  probe                     Check WHO_AM_I
+  dump                      Print all registers with decoded bitfields
+  config [OPTIONS]          Apply ranges, DLPF and sample rate (wakes the device,
+                            disables interrupts and FIFO)
+      --gyro 250|500|1000|2000
+      --accel 2|4|8|16
+      --dlpf 0..6
+      --divider N           Sample rate divider
+      --rate HZ             Sample rate, converted to a divider
+  stream [OPTIONS]          Print samples scaled to g, deg/s and deg C
+      --rate HZ             Output rate (default 100)
+      --count N             Stop after N samples (default: run forever)
+      --format csv|ndjson   Output format (default csv)
+  selftest                  Run the datasheet self-test
+  help                      Show this message
+
+Options:
+  --bus PATH                I2C bus device (default /dev/i2c-1)
+  --sim                     Use an in-process simulated device
+  --address ADDR            Device address, decimal or 0x-prefixed hex (default 0x68)
+";
+
+enum Bus {
+    Device(String),
+    Sim,
+}
+
+enum Format {
+    Csv,
+    Ndjson,
+}
+
+enum Command {
+    Probe,
+    Dump,
+    Config {
+        gyro: GyroConfig,
+        accel: AccelConfig,
+        dlpf: DlpfConfig,
+        divider: Option<u8>,
+        rate: Option<u32>,
+    },
+    Stream {
+        rate: u32,
+        count: Option<u64>,
+        format: Format,
+    },
+    SelfTest,
+    Help,
+}
+
+struct Options {
+    bus: Bus,
+    address: SevenBitAddress,
+    command: Command,
+}
+
+fn parse_number<T: TryFrom<u64>>(value: &str) -> Result<T, String> {
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    parsed
+        .ok()
+        .and_then(|number| T::try_from(number).ok())
+        .ok_or_else(|| format!("invalid number: {value}"))
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut bus = Bus::Device(String::from("/dev/i2c-1"));
+    let mut address = 0x68;
+    let mut command = None;
+
+    let mut gyro = GyroConfig::Dps250;
+    let mut accel = AccelConfig::Range2G;
+    let mut dlpf = DlpfConfig::Dlpf0;
+    let mut divider = None;
+    let mut rate = None;
+    let mut count = None;
+    let mut format = Format::Csv;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .map(String::as_str)
+                .ok_or_else(|| format!("missing value for {arg}"))
+        };
+        match arg.as_str() {
+            "--bus" => bus = Bus::Device(value()?.to_owned()),
+            "--sim" => bus = Bus::Sim,
+            "--address" => address = parse_number(value()?)?,
+            "--gyro" => {
+                gyro = match value()? {
+                    "250" => GyroConfig::Dps250,
+                    "500" => GyroConfig::Dps500,
+                    "1000" => GyroConfig::Dps1000,
+                    "2000" => GyroConfig::Dps2000,
+                    other => return Err(format!("invalid gyro range: {other}")),
+                }
+            }
+            "--accel" => {
+                accel = match value()? {
+                    "2" => AccelConfig::Range2G,
+                    "4" => AccelConfig::Range4G,
+                    "8" => AccelConfig::Range8G,
+                    "16" => AccelConfig::Range16G,
+                    other => return Err(format!("invalid accel range: {other}")),
+                }
+            }
+            "--dlpf" => {
+                dlpf = match value()? {
+                    "0" => DlpfConfig::Dlpf0,
+                    "1" => DlpfConfig::Dlpf1,
+                    "2" => DlpfConfig::Dlpf2,
+                    "3" => DlpfConfig::Dlpf3,
+                    "4" => DlpfConfig::Dlpf4,
+                    "5" => DlpfConfig::Dlpf5,
+                    "6" => DlpfConfig::Dlpf6,
+                    other => return Err(format!("invalid DLPF setting: {other}")),
+                }
+            }
+            "--divider" => divider = Some(parse_number(value()?)?),
+            "--rate" => rate = Some(parse_number(value()?)?),
+            "--count" => count = Some(parse_number(value()?)?),
+            "--format" => {
+                format = match value()? {
+                    "csv" => Format::Csv,
+                    "ndjson" => Format::Ndjson,
+                    other => return Err(format!("invalid format: {other}")),
+                }
+            }
+            "probe" => command = Some("probe"),
+            "dump" => command = Some("dump"),
+            "config" => command = Some("config"),
+            "stream" => command = Some("stream"),
+            "selftest" => command = Some("selftest"),
+            "help" | "--help" | "-h" => command = Some("help"),
+            other => return Err(format!("unknown argument: {other}")),
+        }
+    }
+
+    let command = match command {
+        Some("probe") => Command::Probe,
+        Some("dump") => Command::Dump,
+        Some("config") => Command::Config {
+            gyro,
+            accel,
+            dlpf,
+            divider,
+            rate,
+        },
+        Some("stream") => Command::Stream {
+            rate: rate.unwrap_or(100),
+            count,
+            format,
+        },
+        Some("selftest") => Command::SelfTest,
+        Some(_) => Command::Help,
+        None => return Err(String::from("no command given")),
+    };
+    Ok(Options {
+        bus,
+        address,
+        command,
+    })
+}
+
+/// Blocking delay backed by `std::thread::sleep`
+struct StdDelay;
+
+impl DelayNs for StdDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        thread::sleep(Duration::from_nanos(ns as u64));
+    }
+}
+
+/// Simulated device whose clock follows wall time
+///
+/// Before every transaction the simulator is advanced by the time elapsed since the
+/// previous one, so the sample rate and FIFO fill behave like real hardware.
+struct WallClockSim {
+    sim: SimulatedMpu6050,
+    last: Instant,
+}
+
+impl WallClockSim {
+    fn new(address: SevenBitAddress) -> Self {
+        let mut sim = SimulatedMpu6050::new(address);
+        sim.set_accel_raw(0, 0, 16384);
+        sim.set_gyro_raw(0, 0, 0);
+        sim.set_temperature_celsius(25.0);
+        Self {
+            sim,
+            last: Instant::now(),
+        }
+    }
+}
+
+impl ErrorType for WallClockSim {
+    type Error = SimError;
+}
+
+impl I2c for WallClockSim {
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last).as_micros();
+        self.sim.advance_us(elapsed.min(u32::MAX as u128) as u32);
+        self.last = now;
+        self.sim.transaction(address, operations)
+    }
+}
+
+/// Make sure the device is out of sleep mode before reading samples
+fn ensure_awake<I2C: I2c>(mpu: &mut Mpu6050<I2C>) -> Result<(), String> {
+    let pwr_mgmt_1 = mpu.read_power_management().map_err(|e| e.to_string())?;
+    if pwr_mgmt_1.is_sleep() {
+        eprintln!("device is sleeping, waking it up");
+        mpu.wake(&mut StdDelay).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn probe<I2C: I2c>(mpu: &mut Mpu6050<I2C>) -> Result<(), String> {
+    let variant = mpu.variant();
+    println!(
+        "{variant:?} found (WHO_AM_I = 0x{:02X})",
+        variant.who_am_i()
+    );
+    Ok(())
+}
+
+fn dump<I2C: I2c>(mpu: &mut Mpu6050<I2C>) -> Result<(), String> {
+    let registers = mpu.dump_registers().map_err(|e| e.to_string())?;
+    print!("{registers}");
+    Ok(())
+}
+
+fn config<I2C: I2c>(
+    mpu: &mut Mpu6050<I2C>,
+    gyro: GyroConfig,
+    accel: AccelConfig,
+    dlpf: DlpfConfig,
+    divider: Option<u8>,
+    rate: Option<u32>,
+) -> Result<(), String> {
+    let divider = match (divider, rate) {
+        (Some(divider), _) => divider,
+        (None, Some(0)) => return Err(String::from("rate must be greater than zero")),
+        (None, Some(rate)) => {
+            let divider = (dlpf.gyro_output_rate_hz() / rate).saturating_sub(1);
+            u8::try_from(divider).map_err(|_| format!("rate {rate} Hz is too low"))?
+        }
+        (None, None) => 0,
+    };
+    let device_config = DeviceConfig::new()
+        .with_gyro(gyro)
+        .with_accel(accel)
+        .with_dlpf(dlpf)
+        .with_sample_rate_divider(divider);
+    mpu.apply_config(&device_config)
+        .map_err(|e| e.to_string())?;
+    println!(
+        "applied: gyro ±{} °/s, accel ±{} g, DLPF {}, sample rate {} Hz",
+        gyro.scale_range(),
+        accel.scale_range(),
+        dlpf.register_value(),
+        device_config.sample_rate_hz()
+    );
+    Ok(())
+}
+
+fn stream<I2C: I2c>(
+    mpu: &mut Mpu6050<I2C>,
+    rate: u32,
+    count: Option<u64>,
+    format: Format,
+) -> Result<(), String> {
+    if rate == 0 {
+        return Err(String::from("rate must be greater than zero"));
+    }
+    ensure_awake(mpu)?;
+
+    let gyro = mpu.read_gyro_config().map_err(|e| e.to_string())?;
+    let accel = mpu.read_accel_config().map_err(|e| e.to_string())?;
+    let gyro_scale = gyro.scale_range() as f32 / 32768.0;
+    let accel_scale = accel.scale_range() as f32 / 32768.0;
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    if let Format::Csv = format {
+        writeln!(out, "t_us,ax,ay,az,gx,gy,gz,temp").map_err(|e| e.to_string())?;
+    }
+
+    let period = Duration::from_secs(1) / rate;
+    let start = Instant::now();
+    let mut next = start;
+    let mut written = 0u64;
+    while count.is_none_or(|count| written < count) {
+        let sample = mpu.read_sample().map_err(|e| e.to_string())?;
+        let t_us = start.elapsed().as_micros();
+        let (accel, gyro) = (sample.accel, sample.gyro);
+        let temp = sample.temp.celsius_f32_for(mpu.variant());
+
+        let (ax, ay, az) = (
+            accel.x as f32 * accel_scale,
+            accel.y as f32 * accel_scale,
+            accel.z as f32 * accel_scale,
+        );
+        let (gx, gy, gz) = (
+            gyro.x as f32 * gyro_scale,
+            gyro.y as f32 * gyro_scale,
+            gyro.z as f32 * gyro_scale,
+        );
+        let line = match format {
+            Format::Csv => writeln!(
+                out,
+                "{t_us},{ax:.5},{ay:.5},{az:.5},{gx:.4},{gy:.4},{gz:.4},{temp:.2}"
+            ),
+            Format::Ndjson => writeln!(
+                out,
+                "{{\"t_us\":{t_us},\"accel\":[{ax:.5},{ay:.5},{az:.5}],\"gyro\":[{gx:.4},{gy:.4},{gz:.4}],\"temp\":{temp:.2}}}"
+            ),
+        };
+        line.and_then(|_| out.flush()).map_err(|e| e.to_string())?;
+        written += 1;
+
+        next += period;
+        if let Some(wait) = next.checked_duration_since(Instant::now()) {
+            thread::sleep(wait);
+        }
+    }
+    Ok(())
+}
+
+fn selftest<I2C: I2c>(mpu: &mut Mpu6050<I2C>) -> Result<(), String> {
+    ensure_awake(mpu)?;
+    let result = mpu
+        .self_test(&mut StdDelay)
+        .map_err(|e| e.to_string())?;
+    for (name, deviation) in ["accel x", "accel y", "accel z"]
+        .iter()
+        .zip(result.accel)
+        .chain(["gyro x", "gyro y", "gyro z"].iter().zip(result.gyro))
+    {
+        println!("{name}: {:+.1}% from factory trim", deviation * 100.0);
+    }
+    if result.passed() {
+        println!("self-test passed");
+        Ok(())
+    } else {
+        Err(String::from("self-test failed"))
+    }
+}
+
+fn run<I2C: I2c>(i2c: I2C, address: SevenBitAddress, command: Command) -> Result<(), String> {
+    if let Command::Help = command {
+        print!("{USAGE}");
+        return Ok(());
+    }
+    // Every command depends on the variant: offsets, temperature formula and self-test
+    let mut mpu = Mpu6050::new(i2c, address);
+    mpu.detect_variant().map_err(|e| e.to_string())?;
+    match command {
+        Command::Probe => probe(&mut mpu),
+        Command::Dump => dump(&mut mpu),
+        Command::Config {
+            gyro,
+            accel,
+            dlpf,
+            divider,
+            rate,
+        } => config(&mut mpu, gyro, accel, dlpf, divider, rate),
+        Command::Stream {
+            rate,
+            count,
+            format,
+        } => stream(&mut mpu, rate, count, format),
+        Command::SelfTest => selftest(&mut mpu),
+        Command::Help => unreachable!("handled above"),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match options.bus {
+        Bus::Sim => run(
+            WallClockSim::new(options.address),
+            options.address,
+            options.command,
+        ),
+        Bus::Device(path) => match I2cdev::new(&path) {
+            Ok(i2c) => run(i2c, options.address, options.command),
+            Err(e) => Err(format!("cannot open {path}: {e}")),
+        },
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mpu6050::mpu6050::ChipVariant;
+
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    fn run_sim(line: &str) -> Result<(), String> {
+        run_on(WallClockSim::new(0x68), line)
+    }
+
+    fn run_on(sim: WallClockSim, line: &str) -> Result<(), String> {
+        let options = parse_args(&args(line))?;
+        assert!(matches!(options.bus, Bus::Sim));
+        run(sim, options.address, options.command)
+    }
+
+    #[test]
+    fn sim_probe() {
+        run_sim("--sim probe").unwrap();
+    }
+
+    #[test]
+    fn sim_detects_variant_before_commands() {
+        let mpu6500 = || {
+            let mut sim = WallClockSim::new(0x68);
+            sim.sim.poke(0x75, ChipVariant::Mpu6500.who_am_i());
+            sim
+        };
+        run_on(mpu6500(), "--sim probe").unwrap();
+        run_on(mpu6500(), "--sim stream --count 2").unwrap();
+        let error = run_on(mpu6500(), "--sim selftest").unwrap_err();
+        assert!(error.contains("not supported by this chip variant"), "{error}");
+
+        let mut sim = WallClockSim::new(0x68);
+        sim.sim.poke(0x75, 0x00);
+        assert!(run_on(sim, "--sim dump").is_err());
+    }
+
+    #[test]
+    fn sim_wrong_address_fails() {
+        assert!(run_sim("--sim --address 0x69 probe").is_err());
+    }
+
+    #[test]
+    fn sim_config_and_stream() {
+        let mut mpu = Mpu6050::new(WallClockSim::new(0x68), 0x68);
+        config(
+            &mut mpu,
+            GyroConfig::Dps500,
+            AccelConfig::Range4G,
+            DlpfConfig::Dlpf1,
+            None,
+            Some(100),
+        )
+        .unwrap();
+        assert_eq!(mpu.read_sample_rate_hz().unwrap(), 100);
+        assert_eq!(mpu.read_gyro_config().unwrap(), GyroConfig::Dps500);
+
+        stream(&mut mpu, 1000, Some(3), Format::Csv).unwrap();
+        // Let the simulator produce a sample at 100 Hz
+        thread::sleep(Duration::from_millis(20));
+        let sample = mpu.read_sample().unwrap();
+        // Raw values set up by WallClockSim::new
+        assert_eq!(sample.accel.z, 16384);
+        assert!((sample.temp.celsius_f32() - 25.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn sim_stream_wakes_device() {
+        let mut mpu = Mpu6050::new(WallClockSim::new(0x68), 0x68);
+        stream(&mut mpu, 1000, Some(1), Format::Ndjson).unwrap();
+        assert!(!mpu.read_power_management().unwrap().is_sleep());
+    }
+
+    #[test]
+    fn sim_selftest() {
+        run_sim("--sim selftest").unwrap();
+    }
+
+    #[test]
+    fn rejects_unknown_arguments() {
+        assert!(parse_args(&args("--sim frobnicate")).is_err());
+        assert!(parse_args(&args("--sim")).is_err());
+        assert!(parse_args(&args("--gyro 300 config")).is_err());
+    }
+}
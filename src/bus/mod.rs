@@ -0,0 +1,6 @@
+
+mod register_bus;
+mod spi;
+
+pub use register_bus::RegisterBus;
+pub use spi::SpiInterface;
@@ -0,0 +1,50 @@
+use embedded_hal::i2c::SevenBitAddress;
+
+/// Register level access to the device
+///
+/// Implemented for every [`embedded_hal::i2c::I2c`] bus and for [`SpiInterface`](crate::bus::SpiInterface)
+/// (MPU-6000 only). The register map is the same on both buses.
+pub trait RegisterBus {
+    type Error;
+
+    /// Burst read `buffer.len()` bytes starting at `register`
+    fn read_registers(
+        &mut self,
+        address: SevenBitAddress,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Write a single register
+    fn write_register(
+        &mut self,
+        address: SevenBitAddress,
+        register: u8,
+        value: u8,
+    ) -> Result<(), Self::Error>;
+}
+
+impl<I2C> RegisterBus for I2C
+where
+    I2C: embedded_hal::i2c::I2c,
+{
+    type Error = I2C::Error;
+
+    fn read_registers(
+        &mut self,
+        address: SevenBitAddress,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.write_read(address, &[register], buffer)
+    }
+
+    fn write_register(
+        &mut self,
+        address: SevenBitAddress,
+        register: u8,
+        value: u8,
+    ) -> Result<(), Self::Error> {
+        self.write(address, &[register, value])
+    }
+}
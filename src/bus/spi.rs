@@ -0,0 +1,93 @@
+use embedded_hal::i2c::SevenBitAddress;
+use embedded_hal::spi::{Operation, SpiDevice};
+
+use crate::bus::RegisterBus;
+
+/// Set in the first byte of a transfer to read the register
+const READ_BIT: u8 = 0x80;
+
+/// First and last register that may be read at up to 20 MHz: INT_STATUS, the sensor data and
+/// EXT_SENS_DATA. All other accesses, and all writes, are limited to 1 MHz.
+const FAST_READ_START: u8 = 0x3A;
+const FAST_READ_END: u8 = 0x60;
+
+/// SPI interface of the MPU-6000
+///
+/// [`SpiDevice`] has no way to change the clock, so the speed limits are met with up to two
+/// devices on the same bus and chip select:
+/// - `config`, clocked at 1 MHz or less, used for every write and most reads
+/// - `data`, clocked at 20 MHz or less, used only for reads of the interrupt status and sensor
+///   data registers
+///
+/// Without a `data` device, everything goes through `config`. The I2C address passed to
+/// [`RegisterBus`] is ignored.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct SpiInterface<SPI> {
+    config: SPI,
+    data: Option<SPI>,
+}
+
+impl<SPI> SpiInterface<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Use a single device for all transfers, which must be clocked at 1 MHz or less
+    pub const fn new(spi: SPI) -> Self {
+        Self {
+            config: spi,
+            data: None,
+        }
+    }
+
+    /// Use `config` (1 MHz max) for register access and `data` (20 MHz max) for sensor data reads
+    pub const fn with_data_device(config: SPI, data: SPI) -> Self {
+        Self {
+            config,
+            data: Some(data),
+        }
+    }
+
+    /// Destroy the interface and return the config and data devices
+    pub fn release(self) -> (SPI, Option<SPI>) {
+        (self.config, self.data)
+    }
+
+    /// Check if a burst read of `len` bytes from `register` may run at the data register speed
+    const fn is_fast_read(register: u8, len: usize) -> bool {
+        let end = register as usize + len;
+        register >= FAST_READ_START && len > 0 && end - 1 <= FAST_READ_END as usize
+    }
+}
+
+impl<SPI> RegisterBus for SpiInterface<SPI>
+where
+    SPI: SpiDevice,
+{
+    type Error = SPI::Error;
+
+    fn read_registers(
+        &mut self,
+        _address: SevenBitAddress,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        let spi = match &mut self.data {
+            Some(data) if Self::is_fast_read(register, buffer.len()) => data,
+            _ => &mut self.config,
+        };
+        spi.transaction(&mut [
+            Operation::Write(&[register | READ_BIT]),
+            Operation::Read(buffer),
+        ])
+    }
+
+    fn write_register(
+        &mut self,
+        _address: SevenBitAddress,
+        register: u8,
+        value: u8,
+    ) -> Result<(), Self::Error> {
+        self.config.write(&[register & !READ_BIT, value])
+    }
+}
@@ -0,0 +1,75 @@
+use crate::config::Orientation;
+use crate::mpu6050::{Sample, Vector3};
+
+/// Static offsets subtracted from raw accelerometer and gyroscope readings
+///
+/// Offsets are in raw LSB at the full scale range they were measured with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Calibration {
+    pub accel_offset: Vector3,
+    pub gyro_offset: Vector3,
+}
+
+impl Calibration {
+    /// Create calibration with zero offsets
+    pub const fn new() -> Self {
+        Self {
+            accel_offset: Vector3 { x: 0, y: 0, z: 0 },
+            gyro_offset: Vector3 { x: 0, y: 0, z: 0 },
+        }
+    }
+
+    pub const fn with_accel_offset(mut self, offset: Vector3) -> Self {
+        self.accel_offset = offset;
+        self
+    }
+
+    pub const fn with_gyro_offset(mut self, offset: Vector3) -> Self {
+        self.gyro_offset = offset;
+        self
+    }
+
+    /// Subtract the accelerometer offset from a raw reading
+    pub const fn apply_accel(&self, accel: Vector3) -> Vector3 {
+        subtract(accel, self.accel_offset)
+    }
+
+    /// Subtract the gyroscope offset from a raw reading
+    pub const fn apply_gyro(&self, gyro: Vector3) -> Vector3 {
+        subtract(gyro, self.gyro_offset)
+    }
+
+    /// Rotate offsets measured in the sensor frame into the body frame, for use with a driver
+    /// that has [`Orientation`] set
+    pub fn to_body_frame(&self, orientation: &Orientation) -> Self {
+        Self {
+            accel_offset: orientation.apply(self.accel_offset),
+            gyro_offset: orientation.apply(self.gyro_offset),
+        }
+    }
+
+    /// Subtract both offsets from a sample
+    pub const fn apply(&self, sample: Sample) -> Sample {
+        Sample {
+            accel: self.apply_accel(sample.accel),
+            temp: sample.temp,
+            gyro: self.apply_gyro(sample.gyro),
+        }
+    }
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const fn subtract(value: Vector3, offset: Vector3) -> Vector3 {
+    Vector3 {
+        x: value.x.saturating_sub(offset.x),
+        y: value.y.saturating_sub(offset.y),
+        z: value.z.saturating_sub(offset.z),
+    }
+}
@@ -0,0 +1,439 @@
+use crate::mpu6050::{round_to_i16, ChipVariant, Temperature, Vector3};
+
+/// Highest supported polynomial degree
+pub const MAX_DEGREE: usize = 3;
+
+/// Temperature differences are divided by this before fitting to keep the
+/// normal equations well conditioned.
+const TEMP_SCALE: f64 = 10.0;
+
+/// First byte of a serialised model. Starts above [`MAX_DEGREE`] so models stored in the
+/// unversioned layout, which starts with the degree, can be told apart.
+const FORMAT_VERSION: u8 = 0x10;
+
+/// Length of the unversioned layout: degree, reference temperature and coefficients
+const UNVERSIONED_LEN: usize = 1 + 4 + 3 * (MAX_DEGREE + 1) * 4;
+
+/// Pivots smaller than this fraction of the largest matrix entry mark a singular fit. Well above
+/// the rounding error the running sums accumulate over millions of samples.
+const PIVOT_TOLERANCE: f64 = 1e-9;
+
+/// Polynomial gyroscope bias-vs-temperature model
+///
+/// For each axis the bias in raw LSB is modelled as
+/// `c0 + c1·dt + c2·dt² + c3·dt³`, where `dt` is the difference between the die temperature
+/// and the reference temperature in °C. The die temperature is converted with the formula of
+/// the family member identified by WHO_AM_I, the MPU-6050 unless set otherwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GyroBiasModel {
+    degree: u8,
+    reference_temp: f32,
+    coefficients: [[f32; MAX_DEGREE + 1]; 3],
+    who_am_i: u8,
+}
+
+impl GyroBiasModel {
+    /// Length of the serialised model in bytes
+    pub const SERIALIZED_LEN: usize = 3 + 4 + 3 * (MAX_DEGREE + 1) * 4;
+
+    /// Create model from coefficients
+    ///
+    /// `coefficients[axis][k]` is the coefficient of `dt^k` for axis X, Y, Z.
+    /// Returns `None` if `degree` is larger than [`MAX_DEGREE`].
+    pub const fn new(
+        degree: u8,
+        reference_temp: f32,
+        coefficients: [[f32; MAX_DEGREE + 1]; 3],
+    ) -> Option<Self> {
+        if degree as usize > MAX_DEGREE {
+            return None;
+        }
+        Some(Self {
+            degree,
+            reference_temp,
+            coefficients,
+            who_am_i: ChipVariant::Mpu6050.who_am_i(),
+        })
+    }
+
+    /// Set the WHO_AM_I value of the device the model applies to
+    pub const fn with_who_am_i(mut self, who_am_i: u8) -> Self {
+        self.who_am_i = who_am_i;
+        self
+    }
+
+    /// Get polynomial degree
+    pub const fn degree(&self) -> u8 {
+        self.degree
+    }
+
+    /// Get reference temperature in °C
+    pub const fn reference_temp(&self) -> f32 {
+        self.reference_temp
+    }
+
+    /// Get WHO_AM_I value of the device the model applies to
+    pub const fn who_am_i(&self) -> u8 {
+        self.who_am_i
+    }
+
+    /// Get coefficients, indexed by axis and power of `dt`
+    pub const fn coefficients(&self) -> &[[f32; MAX_DEGREE + 1]; 3] {
+        &self.coefficients
+    }
+
+    /// Get modelled bias in raw LSB for X, Y and Z at the given temperature
+    pub fn bias(&self, temp: Temperature) -> [f32; 3] {
+        let dt = celsius(temp, self.who_am_i) - self.reference_temp;
+        let mut bias = [0.0f32; 3];
+        for (axis, coefficients) in self.coefficients.iter().enumerate() {
+            // Horner's method
+            let mut value = 0.0f32;
+            for k in (0..=self.degree as usize).rev() {
+                value = value * dt + coefficients[k];
+            }
+            bias[axis] = value;
+        }
+        bias
+    }
+
+    /// Subtract the modelled bias from a raw gyroscope reading
+    pub fn correct(&self, gyro: Vector3, temp: Temperature) -> Vector3 {
+        let bias = self.bias(temp);
+        Vector3 {
+            x: round_to_i16(gyro.x as f32 - bias[0]),
+            y: round_to_i16(gyro.y as f32 - bias[1]),
+            z: round_to_i16(gyro.z as f32 - bias[2]),
+        }
+    }
+
+    /// Serialise model for storage
+    ///
+    /// Layout: format version (u8), degree (u8), WHO_AM_I (u8), reference temperature (f32),
+    /// then the coefficients of X, Y and Z in ascending power. All values are little-endian.
+    pub fn to_bytes(&self) -> [u8; Self::SERIALIZED_LEN] {
+        let mut bytes = [0u8; Self::SERIALIZED_LEN];
+        bytes[0] = FORMAT_VERSION;
+        bytes[1] = self.degree;
+        bytes[2] = self.who_am_i;
+        bytes[3..7].copy_from_slice(&self.reference_temp.to_le_bytes());
+        let values = self.coefficients.iter().flatten();
+        for (chunk, value) in bytes[7..].chunks_exact_mut(4).zip(values) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Deserialise model stored with [`GyroBiasModel::to_bytes`]
+    ///
+    /// Models stored in the earlier unversioned layout (degree, reference temperature,
+    /// coefficients) are read as MPU-6050 models. Returns `None` if the slice is too short,
+    /// the format version is unknown or the degree is not supported.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (degree, who_am_i, values) = match *bytes.first()? {
+            FORMAT_VERSION if bytes.len() >= Self::SERIALIZED_LEN => {
+                (bytes[1], bytes[2], &bytes[3..Self::SERIALIZED_LEN])
+            }
+            degree if degree as usize <= MAX_DEGREE && bytes.len() >= UNVERSIONED_LEN => (
+                degree,
+                ChipVariant::Mpu6050.who_am_i(),
+                &bytes[1..UNVERSIONED_LEN],
+            ),
+            _ => return None,
+        };
+        let read_f32 = |offset: usize| {
+            f32::from_le_bytes([
+                values[offset],
+                values[offset + 1],
+                values[offset + 2],
+                values[offset + 3],
+            ])
+        };
+        let mut coefficients = [[0.0f32; MAX_DEGREE + 1]; 3];
+        for (i, value) in coefficients.iter_mut().flatten().enumerate() {
+            *value = read_f32(4 + i * 4);
+        }
+        let model = Self::new(degree, read_f32(0), coefficients)?;
+        Some(model.with_who_am_i(who_am_i))
+    }
+}
+
+/// Least-squares fitter for [`GyroBiasModel`]
+///
+/// Collect `read_gyro`/`read_temp` pairs while the device is stationary and the temperature
+/// sweeps through the operating range, then call [`GyroBiasFitter::fit`]. Only running sums are
+/// stored, so any number of samples can be added.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct GyroBiasFitter {
+    degree: u8,
+    reference_temp: f32,
+    who_am_i: u8,
+    samples: u32,
+    /// Sums of x^k for k = 0..=2·degree
+    power_sums: [f64; 2 * MAX_DEGREE + 1],
+    /// Sums of bias·x^k per axis for k = 0..=degree
+    moment_sums: [[f64; MAX_DEGREE + 1]; 3],
+}
+
+impl GyroBiasFitter {
+    /// Create fitter for a polynomial of the given degree around `reference_temp` (°C)
+    ///
+    /// The degree is clamped to [`MAX_DEGREE`].
+    pub const fn new(degree: u8, reference_temp: f32) -> Self {
+        let degree = if degree as usize > MAX_DEGREE {
+            MAX_DEGREE as u8
+        } else {
+            degree
+        };
+        Self {
+            degree,
+            reference_temp,
+            who_am_i: ChipVariant::Mpu6050.who_am_i(),
+            samples: 0,
+            power_sums: [0.0; 2 * MAX_DEGREE + 1],
+            moment_sums: [[0.0; MAX_DEGREE + 1]; 3],
+        }
+    }
+
+    /// Set the WHO_AM_I value of the device the samples are taken from, it selects the
+    /// temperature conversion and is stored in the fitted model
+    pub const fn with_who_am_i(mut self, who_am_i: u8) -> Self {
+        self.who_am_i = who_am_i;
+        self
+    }
+
+    /// Get number of samples added so far
+    pub const fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// Add a stationary gyroscope reading taken at the given temperature
+    pub fn add_sample(&mut self, gyro: Vector3, temp: Temperature) {
+        let x = (celsius(temp, self.who_am_i) - self.reference_temp) as f64 / TEMP_SCALE;
+        let bias = [gyro.x as f64, gyro.y as f64, gyro.z as f64];
+
+        let mut power = 1.0f64;
+        for k in 0..=2 * self.degree as usize {
+            self.power_sums[k] += power;
+            if k <= self.degree as usize {
+                for (sums, bias) in self.moment_sums.iter_mut().zip(bias) {
+                    sums[k] += bias * power;
+                }
+            }
+            power *= x;
+        }
+        self.samples += 1;
+    }
+
+    /// Discard all collected samples
+    pub fn reset(&mut self) {
+        *self = Self::new(self.degree, self.reference_temp).with_who_am_i(self.who_am_i);
+    }
+
+    /// Fit the model
+    ///
+    /// Returns `None` if there are fewer samples than coefficients or the samples don't span
+    /// enough distinct temperatures to determine the polynomial.
+    pub fn fit(&self) -> Option<GyroBiasModel> {
+        let n = self.degree as usize + 1;
+        if (self.samples as usize) < n {
+            return None;
+        }
+
+        let mut coefficients = [[0.0f32; MAX_DEGREE + 1]; 3];
+        for (axis_coefficients, moment_sums) in coefficients.iter_mut().zip(&self.moment_sums) {
+            let mut matrix = [[0.0f64; MAX_DEGREE + 2]; MAX_DEGREE + 1];
+            for (row, matrix_row) in matrix.iter_mut().enumerate().take(n) {
+                matrix_row[..n].copy_from_slice(&self.power_sums[row..row + n]);
+                matrix_row[n] = moment_sums[row];
+            }
+            let solution = solve(&mut matrix, n)?;
+
+            // Undo temperature scaling so coefficients apply to dt in °C
+            let mut scale = 1.0f64;
+            for (coefficient, value) in axis_coefficients.iter_mut().zip(&solution[..n]) {
+                *coefficient = (value / scale) as f32;
+                scale *= TEMP_SCALE;
+            }
+        }
+
+        GyroBiasModel::new(self.degree, self.reference_temp, coefficients)
+            .map(|model| model.with_who_am_i(self.who_am_i))
+    }
+}
+
+/// Die temperature in °C, converted for the family member identified by `who_am_i`
+fn celsius(temp: Temperature, who_am_i: u8) -> f32 {
+    let variant = ChipVariant::from_who_am_i(who_am_i).unwrap_or_default();
+    temp.celsius_f32_for(variant)
+}
+
+/// Solve an `n`×`n` linear system given as augmented matrix using Gaussian elimination
+/// with partial pivoting.
+///
+/// Returns `None` if a pivot is negligible relative to the largest coefficient.
+fn solve(
+    matrix: &mut [[f64; MAX_DEGREE + 2]; MAX_DEGREE + 1],
+    n: usize,
+) -> Option<[f64; MAX_DEGREE + 1]> {
+    let largest = matrix[..n]
+        .iter()
+        .flat_map(|row| &row[..n])
+        .fold(0.0f64, |largest, value| largest.max(libm::fabs(*value)));
+    let tolerance = largest * PIVOT_TOLERANCE;
+
+    for col in 0..n {
+        let mut pivot = col;
+        for row in col + 1..n {
+            if libm::fabs(matrix[row][col]) > libm::fabs(matrix[pivot][col]) {
+                pivot = row;
+            }
+        }
+        if libm::fabs(matrix[pivot][col]) <= tolerance {
+            return None;
+        }
+        matrix.swap(col, pivot);
+
+        let (upper, lower) = matrix.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for row in lower.iter_mut().take(n - col - 1) {
+            let factor = row[col] / pivot_row[col];
+            for (value, pivot_value) in row[col..=n].iter_mut().zip(&pivot_row[col..=n]) {
+                *value -= factor * pivot_value;
+            }
+        }
+    }
+
+    let mut solution = [0.0f64; MAX_DEGREE + 1];
+    for row in (0..n).rev() {
+        let mut value = matrix[row][n];
+        for k in row + 1..n {
+            value -= matrix[row][k] * solution[k];
+        }
+        solution[row] = value / matrix[row][row];
+    }
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bias of the test polynomial `10 + 2·dt + 0.05·dt²` around 25 °C
+    fn bias_at(temp: Temperature) -> f64 {
+        let dt = temp.celsius_f32() as f64 - 25.0;
+        10.0 + 2.0 * dt + 0.05 * dt * dt
+    }
+
+    fn fitted_model() -> GyroBiasModel {
+        let mut fitter = GyroBiasFitter::new(2, 25.0);
+        for raw in (-8000i16..8000).step_by(50) {
+            let temp = Temperature::from_raw(raw);
+            let bias = bias_at(temp);
+            let gyro = Vector3 {
+                x: bias.round() as i16,
+                y: -5,
+                z: (-bias).round() as i16,
+            };
+            fitter.add_sample(gyro, temp);
+        }
+        fitter.fit().unwrap()
+    }
+
+    #[test]
+    fn fit_recovers_polynomial() {
+        let model = fitted_model();
+        let [x, y, z] = model.coefficients();
+        for (actual, expected) in x.iter().zip([10.0, 2.0, 0.05, 0.0]) {
+            assert!(libm::fabsf(actual - expected) < 0.05, "{x:?}");
+        }
+        assert!(libm::fabsf(y[0] + 5.0) < 1e-3);
+        assert!(y[1..].iter().all(|value| libm::fabsf(*value) < 1e-3));
+        assert!(libm::fabsf(z[1] + 2.0) < 0.05);
+    }
+
+    #[test]
+    fn correct_subtracts_bias() {
+        let model = fitted_model();
+        let temp = Temperature::from_raw(3400);
+        let bias = bias_at(temp).round() as i16;
+        let gyro = Vector3 {
+            x: bias,
+            y: -5,
+            z: -bias,
+        };
+        assert_eq!(model.correct(gyro, temp), Vector3 { x: 0, y: 0, z: 0 });
+    }
+
+    #[test]
+    fn fit_needs_enough_samples() {
+        let mut fitter = GyroBiasFitter::new(2, 25.0);
+        fitter.add_sample(Vector3 { x: 1, y: 1, z: 1 }, Temperature::from_raw(0));
+        fitter.add_sample(Vector3 { x: 1, y: 1, z: 1 }, Temperature::from_raw(340));
+        assert_eq!(fitter.fit(), None);
+    }
+
+    #[test]
+    fn fit_rejects_single_temperature() {
+        // Many samples make the normal equations large, the singularity check has to scale
+        for samples in [2, 100_000, 1_000_000] {
+            let mut fitter = GyroBiasFitter::new(1, 25.0);
+            for _ in 0..samples {
+                fitter.add_sample(Vector3 { x: 7, y: 7, z: 7 }, Temperature::from_raw(1234));
+            }
+            assert_eq!(fitter.fit(), None, "{samples} samples");
+        }
+    }
+
+    #[test]
+    fn fit_uses_variant_temperature() {
+        let mpu6500 = ChipVariant::Mpu6500;
+        let mut fitter = GyroBiasFitter::new(1, 21.0).with_who_am_i(mpu6500.who_am_i());
+        for celsius in [11.0, 21.0, 31.0] {
+            let temp = Temperature::from_celsius_for(celsius, mpu6500);
+            let bias = (celsius - 21.0) * 3.0;
+            let gyro = Vector3 {
+                x: bias as i16,
+                y: 0,
+                z: 0,
+            };
+            fitter.add_sample(gyro, temp);
+        }
+        let model = fitter.fit().unwrap();
+        assert_eq!(model.who_am_i(), 0x70);
+        assert!(libm::fabsf(model.coefficients()[0][0]) < 0.01);
+        assert!(libm::fabsf(model.coefficients()[0][1] - 3.0) < 0.01);
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let model = fitted_model().with_who_am_i(0x12);
+        let bytes = model.to_bytes();
+        assert_eq!(bytes[0], FORMAT_VERSION);
+        assert_eq!(GyroBiasModel::from_bytes(&bytes), Some(model));
+        assert_eq!(GyroBiasModel::from_bytes(&bytes[..bytes.len() - 1]), None);
+    }
+
+    #[test]
+    fn reads_unversioned_layout() {
+        let model = fitted_model();
+        let bytes = model.to_bytes();
+        let mut unversioned = [0u8; UNVERSIONED_LEN];
+        unversioned[0] = model.degree();
+        unversioned[1..].copy_from_slice(&bytes[3..]);
+        assert_eq!(GyroBiasModel::from_bytes(&unversioned), Some(model));
+    }
+
+    #[test]
+    fn rejects_unknown_version_and_degree() {
+        let mut bytes = fitted_model().to_bytes();
+        bytes[0] = FORMAT_VERSION + 1;
+        assert_eq!(GyroBiasModel::from_bytes(&bytes), None);
+        bytes[0] = FORMAT_VERSION;
+        bytes[1] = MAX_DEGREE as u8 + 1;
+        assert_eq!(GyroBiasModel::from_bytes(&bytes), None);
+    }
+}
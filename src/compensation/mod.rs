@@ -0,0 +1,5 @@
+
+pub mod calibration;
+pub mod gyro_bias;
+pub use calibration::Calibration;
+pub use gyro_bias::{GyroBiasFitter, GyroBiasModel};
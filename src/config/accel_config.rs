@@ -1,4 +1,6 @@
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum AccelConfig {
     Range2G = 0x00,
@@ -8,6 +10,16 @@ pub enum AccelConfig {
 }
 
 impl AccelConfig {
+    /// Create from ACCEL_CONFIG register value
+    pub const fn from_register(value: u8) -> Self {
+        match (value >> 3) & 0x03 {
+            0x00 => Self::Range2G,
+            0x01 => Self::Range4G,
+            0x02 => Self::Range8G,
+            _ => Self::Range16G,
+        }
+    }
+
     /// Get register value for ACCEL_CONFIG register
     pub const fn register_value(&self) -> u8 {
         (*self as u8) << 3
@@ -23,3 +35,235 @@ impl AccelConfig {
         }
     }
 }
+
+/// Accelerometer Digital High Pass Filter (ACCEL_HPF)
+///
+/// The high pass filter output is only used by the motion detection logic: MOT_THR is compared
+/// against the filtered acceleration, so the HPF removes gravity and slow tilt changes from the
+/// motion detector. The accelerometer data registers are not filtered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum AccelHpf {
+    /// Filter is reset, its output settles to zero. Motion detection is effectively disabled.
+    Reset = 0x00,
+    /// Cut-off at 5 Hz
+    Hz5 = 0x01,
+    /// Cut-off at 2.5 Hz
+    Hz2_5 = 0x02,
+    /// Cut-off at 1.25 Hz
+    Hz1_25 = 0x03,
+    /// Cut-off at 0.63 Hz
+    Hz0_63 = 0x04,
+    /// The current sample is held as reference, subsequent samples are compared against it.
+    /// Detects any change from the orientation at the time Hold was set.
+    Hold = 0x07,
+}
+
+impl AccelHpf {
+    /// Create from ACCEL_HPF bits of ACCEL_CONFIG register value.
+    /// Returns `None` for the reserved values 5 and 6.
+    pub const fn from_register(value: u8) -> Option<Self> {
+        match value & 0x07 {
+            0x00 => Some(Self::Reset),
+            0x01 => Some(Self::Hz5),
+            0x02 => Some(Self::Hz2_5),
+            0x03 => Some(Self::Hz1_25),
+            0x04 => Some(Self::Hz0_63),
+            0x07 => Some(Self::Hold),
+            _ => None,
+        }
+    }
+
+    /// Convert to register value
+    pub const fn register_value(&self) -> u8 {
+        *self as u8
+    }
+}
+
+/// Complete Accelerometer Configuration (Register 0x1C)
+///
+/// Combines full scale range (AFS_SEL), the self-test bits and the high pass filter mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccelSettings {
+    bits: u8,
+}
+
+impl AccelSettings {
+    /// Create configuration with the given range, self-test off and HPF in reset
+    pub const fn new(range: AccelConfig) -> Self {
+        Self {
+            bits: range.register_value(),
+        }
+    }
+
+    /// Set full scale range
+    ///
+    /// Bits 4:3 of ACCEL_CONFIG register
+    pub const fn with_range(mut self, range: AccelConfig) -> Self {
+        self.bits &= !0x18;
+        self.bits |= range.register_value();
+        self
+    }
+
+    /// Set high pass filter mode
+    ///
+    /// Bits 2:0 of ACCEL_CONFIG register
+    pub const fn with_hpf(mut self, hpf: AccelHpf) -> Self {
+        self.bits &= !0x07;
+        self.bits |= hpf.register_value();
+        self
+    }
+
+    /// Enable X axis self-test
+    ///
+    /// Bit 7 of ACCEL_CONFIG register
+    pub const fn with_self_test_x(mut self, enable: bool) -> Self {
+        if enable {
+            self.bits |= 1 << 7;
+        } else {
+            self.bits &= !(1 << 7);
+        }
+        self
+    }
+
+    /// Enable Y axis self-test
+    ///
+    /// Bit 6 of ACCEL_CONFIG register
+    pub const fn with_self_test_y(mut self, enable: bool) -> Self {
+        if enable {
+            self.bits |= 1 << 6;
+        } else {
+            self.bits &= !(1 << 6);
+        }
+        self
+    }
+
+    /// Enable Z axis self-test
+    ///
+    /// Bit 5 of ACCEL_CONFIG register
+    pub const fn with_self_test_z(mut self, enable: bool) -> Self {
+        if enable {
+            self.bits |= 1 << 5;
+        } else {
+            self.bits &= !(1 << 5);
+        }
+        self
+    }
+
+    /// Helper to enable self-test on all axes at once
+    pub const fn with_self_test_all(self, enable: bool) -> Self {
+        self.with_self_test_x(enable)
+            .with_self_test_y(enable)
+            .with_self_test_z(enable)
+    }
+
+    /// Get register value to write to ACCEL_CONFIG register
+    pub const fn register_value(&self) -> u8 {
+        self.bits
+    }
+
+    /// Create from register value
+    pub const fn from_register(bits: u8) -> Self {
+        Self { bits }
+    }
+
+    /// Get full scale range
+    pub const fn range(&self) -> AccelConfig {
+        AccelConfig::from_register(self.bits)
+    }
+
+    /// Get high pass filter mode, `None` if a reserved value is set
+    pub const fn hpf(&self) -> Option<AccelHpf> {
+        AccelHpf::from_register(self.bits)
+    }
+
+    /// Check if X axis self-test is enabled
+    pub const fn is_self_test_x(&self) -> bool {
+        (self.bits & (1 << 7)) != 0
+    }
+
+    /// Check if Y axis self-test is enabled
+    pub const fn is_self_test_y(&self) -> bool {
+        (self.bits & (1 << 6)) != 0
+    }
+
+    /// Check if Z axis self-test is enabled
+    pub const fn is_self_test_z(&self) -> bool {
+        (self.bits & (1 << 5)) != 0
+    }
+}
+
+impl From<AccelConfig> for AccelSettings {
+    fn from(range: AccelConfig) -> Self {
+        Self::new(range)
+    }
+}
+
+#[cfg(all(test, feature = "sim"))]
+mod tests {
+    use super::*;
+    use crate::config::DeviceConfig;
+    use crate::errors::MPU6050Error;
+    use crate::mpu6050::{ChipVariant, Mpu6050};
+    use crate::sim::SimulatedMpu6050;
+
+    const ACCEL_CONFIG: u8 = 0x1C;
+
+    #[test]
+    fn settings_round_trip() {
+        let settings = AccelSettings::new(AccelConfig::Range8G)
+            .with_hpf(AccelHpf::Hz1_25)
+            .with_self_test_z(true);
+        assert_eq!(settings.register_value(), 0x33);
+
+        let decoded = AccelSettings::from_register(0x33);
+        assert_eq!(decoded.range(), AccelConfig::Range8G);
+        assert_eq!(decoded.hpf(), Some(AccelHpf::Hz1_25));
+        assert!(!decoded.is_self_test_x() && !decoded.is_self_test_y());
+        assert!(decoded.is_self_test_z());
+
+        // Changing the range keeps the filter
+        let settings = settings.with_range(AccelConfig::Range2G);
+        assert_eq!(settings.hpf(), Some(AccelHpf::Hz1_25));
+        assert_eq!(AccelSettings::from_register(0x05).hpf(), None);
+    }
+
+    #[test]
+    fn configure_writes_hpf() {
+        let mut mpu = Mpu6050::new(SimulatedMpu6050::default(), 0x68);
+        let settings = AccelSettings::new(AccelConfig::Range4G).with_hpf(AccelHpf::Hold);
+        mpu.configure_accel_settings(settings).unwrap();
+        let sim = mpu.release();
+        assert_eq!(sim.peek(ACCEL_CONFIG), 0x0F);
+
+        let mut mpu = Mpu6050::new(sim, 0x68);
+        let config = DeviceConfig::new()
+            .with_accel(AccelConfig::Range16G)
+            .with_accel_hpf(AccelHpf::Hz0_63);
+        mpu.apply_config(&config).unwrap();
+        assert_eq!(mpu.release().peek(ACCEL_CONFIG), 0x1C);
+    }
+
+    #[test]
+    fn hpf_unsupported_on_variants_without_it() {
+        let mut mpu = Mpu6050::new(SimulatedMpu6050::default(), 0x68)
+            .with_variant(ChipVariant::Mpu6500);
+        let settings = AccelSettings::new(AccelConfig::Range2G).with_hpf(AccelHpf::Hz5);
+        match mpu.configure_accel_settings(settings) {
+            Err(MPU6050Error::UnsupportedFeature { register }) => {
+                assert_eq!(register, ACCEL_CONFIG)
+            }
+            other => panic!("expected UnsupportedFeature, got {other:?}"),
+        }
+        // A filter in reset is the register's default and accepted
+        mpu.configure_accel_settings(settings.with_hpf(AccelHpf::Reset))
+            .unwrap();
+        let config = DeviceConfig::new().with_accel_hpf(AccelHpf::Hz5);
+        assert!(mpu.apply_config(&config).is_err());
+        assert_eq!(mpu.release().peek(ACCEL_CONFIG), 0x00);
+    }
+}
@@ -0,0 +1,52 @@
+/// Accelerometer low pass filter (ACCEL_CONFIG2, register 0x1D)
+///
+/// Only on the MPU-6500 family, see
+/// [`ChipVariant::has_accel_dlpf`](crate::mpu6050::ChipVariant::has_accel_dlpf). On the MPU-6050
+/// the accelerometer is filtered by [`DlpfConfig`](crate::config::DlpfConfig) together with the
+/// gyroscope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum AccelDlpf {
+    /// 218 Hz bandwidth, 1 kHz output rate
+    Hz218 = 0x00,
+    /// 99 Hz bandwidth, 1 kHz output rate
+    Hz99 = 0x02,
+    /// 45 Hz bandwidth, 1 kHz output rate
+    Hz45 = 0x03,
+    /// 21 Hz bandwidth, 1 kHz output rate
+    Hz21 = 0x04,
+    /// 10 Hz bandwidth, 1 kHz output rate
+    Hz10 = 0x05,
+    /// 5 Hz bandwidth, 1 kHz output rate
+    Hz5 = 0x06,
+    /// 420 Hz bandwidth, 1 kHz output rate
+    Hz420 = 0x07,
+    /// Filter bypassed (ACCEL_FCHOICE_B set), about 1.1 kHz bandwidth, 4 kHz output rate
+    Bypass = 0x08,
+}
+
+impl AccelDlpf {
+    /// Create from ACCEL_CONFIG2 register value.
+    /// A_DLPF_CFG 1 has the same response as 0 and is reported as [`Self::Hz218`].
+    pub const fn from_register(value: u8) -> Self {
+        if value & 0x08 != 0 {
+            return Self::Bypass;
+        }
+        match value & 0x07 {
+            0x00 | 0x01 => Self::Hz218,
+            0x02 => Self::Hz99,
+            0x03 => Self::Hz45,
+            0x04 => Self::Hz21,
+            0x05 => Self::Hz10,
+            0x06 => Self::Hz5,
+            _ => Self::Hz420,
+        }
+    }
+
+    /// Get register value for ACCEL_CONFIG2 register
+    pub const fn register_value(&self) -> u8 {
+        *self as u8
+    }
+}
@@ -1,4 +1,6 @@
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum ClockSource {
     Internal8MHz = 0,
@@ -9,3 +11,20 @@ pub enum ClockSource {
     PllExt19MHz = 5,
     Stop = 7
 }
+
+impl ClockSource {
+    /// Create from CLKSEL bits of PWR_MGMT_1 register value.
+    /// Returns `None` for the reserved value 6.
+    pub const fn from_register(value: u8) -> Option<Self> {
+        match value & 0x07 {
+            0 => Some(Self::Internal8MHz),
+            1 => Some(Self::PllXGyro),
+            2 => Some(Self::PllYGyro),
+            3 => Some(Self::PllZGyro),
+            4 => Some(Self::PllExt32k),
+            5 => Some(Self::PllExt19MHz),
+            7 => Some(Self::Stop),
+            _ => None,
+        }
+    }
+}
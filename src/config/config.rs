@@ -2,6 +2,8 @@
 /// Enables the FSYNC pin to be used as a synchronization signal.
 /// The FSYNC signal is sampled and its edge is detected by the MPU-6050.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum ExtSync {
     /// Input disabled
@@ -23,15 +25,46 @@ pub enum ExtSync {
 }
 
 impl ExtSync {
+    /// Create from EXT_SYNC_SET bits of CONFIG register value
+    pub const fn from_register(value: u8) -> Self {
+        match (value >> 3) & 0x07 {
+            0x00 => Self::Disabled,
+            0x01 => Self::TempOutL,
+            0x02 => Self::GyroXOutL,
+            0x03 => Self::GyroYOutL,
+            0x04 => Self::GyroZOutL,
+            0x05 => Self::AccelXOutL,
+            0x06 => Self::AccelYOutL,
+            _ => Self::AccelZOutL,
+        }
+    }
+
     /// Convert to register value
     pub const fn get_register_value(&self) -> u8 {
         *self as u8
     }
+
+    /// Index of the byte whose LSB carries the FSYNC flag within the 14 bytes
+    /// from ACCEL_XOUT_H to GYRO_ZOUT_L, `None` when FSYNC is disabled
+    pub const fn sample_byte_index(&self) -> Option<usize> {
+        match self {
+            Self::Disabled => None,
+            Self::AccelXOutL => Some(1),
+            Self::AccelYOutL => Some(3),
+            Self::AccelZOutL => Some(5),
+            Self::TempOutL => Some(7),
+            Self::GyroXOutL => Some(9),
+            Self::GyroYOutL => Some(11),
+            Self::GyroZOutL => Some(13),
+        }
+    }
 }
 
 /// Digital Low Pass Filter (DLPF) Configuration
 /// Configures the Digital Low Pass Filter setting for both the gyroscope and accelerometer.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum DlpfConfig {
     /// Accelerometer: bandwidth=260Hz, delay=0ms
@@ -64,6 +97,21 @@ pub enum DlpfConfig {
 }
 
 impl DlpfConfig {
+    /// Create from DLPF_CFG bits of CONFIG register value.
+    /// Returns `None` for the reserved value 7.
+    pub const fn from_register(value: u8) -> Option<Self> {
+        match value & 0x07 {
+            0x00 => Some(Self::Dlpf0),
+            0x01 => Some(Self::Dlpf1),
+            0x02 => Some(Self::Dlpf2),
+            0x03 => Some(Self::Dlpf3),
+            0x04 => Some(Self::Dlpf4),
+            0x05 => Some(Self::Dlpf5),
+            0x06 => Some(Self::Dlpf6),
+            _ => None,
+        }
+    }
+
     /// Get accelerometer bandwidth in Hz
     pub const fn get_accel_bandwidth_hz(&self) -> u16 {
         match self {
@@ -90,6 +138,14 @@ impl DlpfConfig {
         }
     }
 
+    /// Get gyroscope output rate in Hz (8kHz with the DLPF disabled, 1kHz otherwise)
+    pub const fn gyro_output_rate_hz(&self) -> u32 {
+        match self {
+            Self::Dlpf0 => 8000,
+            _ => 1000,
+        }
+    }
+
     /// Convert to register value
     pub const fn register_value(&self) -> u8 {
         *self as u8
@@ -0,0 +1,184 @@
+use crate::bus::RegisterBus;
+use crate::config::{
+    AccelConfig, AccelHpf, AccelSettings, ClockSource, DlpfConfig, ExtSync, FifoConfig, GyroConfig,
+    IntPinConfig, InterruptEnable, PwrMgmt1, PwrMgmt2,
+};
+use crate::errors::MPU6050Error;
+use crate::mpu6050::Mpu6050;
+use crate::registers::Registers;
+
+/// Whole-device configuration
+///
+/// Collects every setting that is normally applied at boot with separate `configure_*` calls,
+/// so it can be written in one go with [`DeviceConfig::apply`] (or [`Mpu6050::apply_config`])
+/// and verified register by register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceConfig {
+    /// Power management (sleep, cycle, temperature sensor). Its clock source bits are
+    /// replaced by `clock_source`.
+    pub pwr_mgmt: PwrMgmt1,
+    /// Cycle mode wake-up frequency and axis standby
+    pub pwr_mgmt_2: PwrMgmt2,
+    pub clock_source: ClockSource,
+    pub gyro: GyroConfig,
+    pub accel: AccelConfig,
+    /// Accelerometer high pass filter feeding the motion detector
+    pub accel_hpf: AccelHpf,
+    pub dlpf: DlpfConfig,
+    pub ext_sync: ExtSync,
+    /// Sample rate = gyroscope output rate / (1 + divider)
+    pub sample_rate_divider: u8,
+    pub int_pin: IntPinConfig,
+    pub int_enable: InterruptEnable,
+    pub fifo: FifoConfig,
+    /// FIFO_EN bit of USER_CTRL
+    pub fifo_enabled: bool,
+}
+
+impl DeviceConfig {
+    /// Create default configuration
+    /// - awake, PLL with X axis gyroscope reference, all axes active
+    /// - ±250 °/s, ±2 g, accelerometer HPF in reset
+    /// - DLPF disabled, FSYNC disabled, no sample rate division
+    /// - interrupts and FIFO disabled
+    pub const fn new() -> Self {
+        Self {
+            pwr_mgmt: PwrMgmt1::new(),
+            pwr_mgmt_2: PwrMgmt2::new(),
+            clock_source: ClockSource::PllXGyro,
+            gyro: GyroConfig::Dps250,
+            accel: AccelConfig::Range2G,
+            accel_hpf: AccelHpf::Reset,
+            dlpf: DlpfConfig::Dlpf0,
+            ext_sync: ExtSync::Disabled,
+            sample_rate_divider: 0,
+            int_pin: IntPinConfig::new(),
+            int_enable: InterruptEnable::empty(),
+            fifo: FifoConfig::new(),
+            fifo_enabled: false,
+        }
+    }
+
+    pub const fn with_pwr_mgmt(mut self, pwr_mgmt: PwrMgmt1) -> Self {
+        self.pwr_mgmt = pwr_mgmt;
+        self
+    }
+
+    pub const fn with_pwr_mgmt_2(mut self, pwr_mgmt_2: PwrMgmt2) -> Self {
+        self.pwr_mgmt_2 = pwr_mgmt_2;
+        self
+    }
+
+    pub const fn with_clock_source(mut self, clock_source: ClockSource) -> Self {
+        self.clock_source = clock_source;
+        self
+    }
+
+    pub const fn with_gyro(mut self, gyro: GyroConfig) -> Self {
+        self.gyro = gyro;
+        self
+    }
+
+    pub const fn with_accel(mut self, accel: AccelConfig) -> Self {
+        self.accel = accel;
+        self
+    }
+
+    pub const fn with_accel_hpf(mut self, accel_hpf: AccelHpf) -> Self {
+        self.accel_hpf = accel_hpf;
+        self
+    }
+
+    pub const fn with_dlpf(mut self, dlpf: DlpfConfig) -> Self {
+        self.dlpf = dlpf;
+        self
+    }
+
+    pub const fn with_ext_sync(mut self, ext_sync: ExtSync) -> Self {
+        self.ext_sync = ext_sync;
+        self
+    }
+
+    pub const fn with_sample_rate_divider(mut self, divider: u8) -> Self {
+        self.sample_rate_divider = divider;
+        self
+    }
+
+    pub const fn with_int_pin(mut self, int_pin: IntPinConfig) -> Self {
+        self.int_pin = int_pin;
+        self
+    }
+
+    pub const fn with_int_enable(mut self, int_enable: InterruptEnable) -> Self {
+        self.int_enable = int_enable;
+        self
+    }
+
+    /// Set which sensors are written to the FIFO and whether the FIFO is enabled
+    pub const fn with_fifo(mut self, fifo: FifoConfig, enabled: bool) -> Self {
+        self.fifo = fifo;
+        self.fifo_enabled = enabled;
+        self
+    }
+
+    /// Sample rate in Hz resulting from the DLPF setting and the sample rate divider
+    pub const fn sample_rate_hz(&self) -> u32 {
+        self.dlpf.gyro_output_rate_hz() / (1 + self.sample_rate_divider as u32)
+    }
+
+    /// Value written to PWR_MGMT_1
+    pub const fn pwr_mgmt_1_value(&self) -> u8 {
+        self.pwr_mgmt.set_clock_source(self.clock_source).register_value()
+    }
+
+    /// Value written to ACCEL_CONFIG
+    pub const fn accel_config_value(&self) -> u8 {
+        AccelSettings::new(self.accel).with_hpf(self.accel_hpf).register_value()
+    }
+
+    /// Value written to CONFIG
+    pub const fn config_value(&self) -> u8 {
+        (self.ext_sync.get_register_value() << 3) | self.dlpf.register_value()
+    }
+
+    /// Check for settings that contradict each other, reported as
+    /// [`MPU6050Error::InvalidConfig`] with the offending register value:
+    /// - DEVICE_RESET set, which would reset the device while the configuration is applied
+    /// - a stopped clock while awake
+    /// - FIFO enabled without any internal sensor written to it, FIFO frames can't be decoded
+    ///   then
+    pub fn validate<E>(&self) -> Result<(), MPU6050Error<E>> {
+        if self.pwr_mgmt.is_device_reset()
+            || (matches!(self.clock_source, ClockSource::Stop) && !self.pwr_mgmt.is_sleep())
+        {
+            return Err(MPU6050Error::InvalidConfig {
+                register: Registers::PowerMgmt1.get_register_address(),
+                value: self.pwr_mgmt_1_value(),
+            });
+        }
+        if self.fifo_enabled && self.fifo.frame_size() == 0 {
+            return Err(MPU6050Error::InvalidConfig {
+                register: Registers::FifoEn.get_register_address(),
+                value: self.fifo.register_value(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Write the configuration to the device and verify every register.
+    /// See [`Mpu6050::apply_config`].
+    pub fn apply<BUS>(&self, mpu: &mut Mpu6050<BUS>) -> Result<(), MPU6050Error<BUS::Error>>
+    where
+        BUS: RegisterBus,
+    {
+        mpu.apply_config(self)
+    }
+}
+
+impl Default for DeviceConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,5 +1,7 @@
 /// Configuration for the FIFO Enable Register (0x23)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FifoConfig {
     bits: u8,
 }
@@ -100,4 +102,73 @@ impl FifoConfig {
     pub const fn register_value(&self) -> u8 {
         self.bits
     }
+
+    /// Create from register value
+    pub const fn from_register(bits: u8) -> Self {
+        Self { bits }
+    }
+
+    /// Check if Temperature FIFO is enabled
+    pub const fn is_temp_enabled(&self) -> bool {
+        (self.bits & (1 << 7)) != 0
+    }
+
+    /// Check if Gyroscope X-axis FIFO is enabled
+    pub const fn is_gyro_x_enabled(&self) -> bool {
+        (self.bits & (1 << 6)) != 0
+    }
+
+    /// Check if Gyroscope Y-axis FIFO is enabled
+    pub const fn is_gyro_y_enabled(&self) -> bool {
+        (self.bits & (1 << 5)) != 0
+    }
+
+    /// Check if Gyroscope Z-axis FIFO is enabled
+    pub const fn is_gyro_z_enabled(&self) -> bool {
+        (self.bits & (1 << 4)) != 0
+    }
+
+    /// Check if Accelerometer FIFO is enabled
+    pub const fn is_accel_enabled(&self) -> bool {
+        (self.bits & (1 << 3)) != 0
+    }
+
+    /// Check if Slave 2 FIFO is enabled
+    pub const fn is_slave2_enabled(&self) -> bool {
+        (self.bits & (1 << 2)) != 0
+    }
+
+    /// Check if Slave 1 FIFO is enabled
+    pub const fn is_slave1_enabled(&self) -> bool {
+        (self.bits & (1 << 1)) != 0
+    }
+
+    /// Check if Slave 0 FIFO is enabled
+    pub const fn is_slave0_enabled(&self) -> bool {
+        (self.bits & (1 << 0)) != 0
+    }
+
+    /// Number of bytes written to the FIFO per sample by the internal sensors:
+    /// 6 for the accelerometer, 2 for temperature and 2 per gyroscope axis.
+    ///
+    /// Data from external slaves is not included, its length is set in I2C_SLVx_CTRL.
+    pub const fn frame_size(&self) -> usize {
+        let mut size = 0;
+        if self.is_accel_enabled() {
+            size += 6;
+        }
+        if self.is_temp_enabled() {
+            size += 2;
+        }
+        if self.is_gyro_x_enabled() {
+            size += 2;
+        }
+        if self.is_gyro_y_enabled() {
+            size += 2;
+        }
+        if self.is_gyro_z_enabled() {
+            size += 2;
+        }
+        size
+    }
 }
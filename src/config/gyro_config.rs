@@ -1,5 +1,7 @@
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum GyroConfig {
     Dps250 = 0x00,
@@ -9,6 +11,16 @@ pub enum GyroConfig {
 }
 
 impl GyroConfig {
+    /// Create from GYRO_CONFIG register value
+    pub const fn from_register(value: u8) -> Self {
+        match (value >> 3) & 0x03 {
+            0x00 => Self::Dps250,
+            0x01 => Self::Dps500,
+            0x02 => Self::Dps1000,
+            _ => Self::Dps2000,
+        }
+    }
+
     /// Get register value for GYRO_CONFIG register
     pub const fn register_value(&self) -> u8 {
         (*self as u8) << 3
@@ -2,6 +2,8 @@
 ///
 /// Configures the behavior of the interrupt pin and other related settings.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IntPinConfig {
     bits: u8,
 }
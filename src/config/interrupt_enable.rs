@@ -3,6 +3,8 @@
 /// Controls which interrupt sources are enabled on the MPU-6050.
 /// Multiple interrupts can be enabled simultaneously by combining flags.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InterruptEnable {
     bits: u8,
 }
@@ -1,18 +1,26 @@
 mod config;
 mod accel_config;
+mod accel_dlpf;
 mod gyro_config;
 mod fifo;
 mod int_pin_config;
 mod interrupt_enable;
 mod pwr_mgmt_1_config;
+mod pwr_mgmt_2_config;
 mod clock_source;
+mod device_config;
+mod orientation;
 
 // Public exports
 pub use config::{ExtSync, DlpfConfig};
-pub use accel_config::AccelConfig;
+pub use accel_config::{AccelConfig, AccelHpf, AccelSettings};
+pub use accel_dlpf::AccelDlpf;
 pub use gyro_config::GyroConfig;
 pub use int_pin_config::IntPinConfig;
 pub use interrupt_enable::InterruptEnable;
 pub use pwr_mgmt_1_config::PwrMgmt1;
+pub use pwr_mgmt_2_config::{LpWakeCtrl, PwrMgmt2};
 pub use clock_source::ClockSource;
 pub use fifo::FifoConfig;
+pub use device_config::DeviceConfig;
+pub use orientation::{AxisRemap, Orientation, SignedAxis};
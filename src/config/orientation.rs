@@ -0,0 +1,260 @@
+use crate::mpu6050::{round_to_i16, Vector3};
+
+/// Sensor axis, optionally negated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignedAxis {
+    PosX,
+    NegX,
+    PosY,
+    NegY,
+    PosZ,
+    NegZ,
+}
+
+impl SignedAxis {
+    /// Index of the sensor axis, 0 for X to 2 for Z
+    pub const fn index(&self) -> usize {
+        match self {
+            Self::PosX | Self::NegX => 0,
+            Self::PosY | Self::NegY => 1,
+            Self::PosZ | Self::NegZ => 2,
+        }
+    }
+
+    /// Check if the axis is negated
+    pub const fn is_negative(&self) -> bool {
+        matches!(self, Self::NegX | Self::NegY | Self::NegZ)
+    }
+
+    const fn pick(&self, vector: [i16; 3]) -> i16 {
+        let value = vector[self.index()];
+        if self.is_negative() {
+            value.saturating_neg()
+        } else {
+            value
+        }
+    }
+}
+
+/// Right-angle mounting orientation
+///
+/// Each body axis is given as the sensor axis pointing the same way. Only the 24 proper
+/// rotations are accepted, mirrored mappings (e.g. negating a single axis) are rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AxisRemap {
+    axes: [SignedAxis; 3],
+}
+
+impl AxisRemap {
+    /// Sensor axes are the body axes
+    pub const IDENTITY: Self = Self {
+        axes: [SignedAxis::PosX, SignedAxis::PosY, SignedAxis::PosZ],
+    };
+
+    /// Sensor mounted upside-down, rotated 180° about X
+    pub const UPSIDE_DOWN: Self = Self {
+        axes: [SignedAxis::PosX, SignedAxis::NegY, SignedAxis::NegZ],
+    };
+
+    /// Create from the sensor axes matching body X, Y and Z.
+    /// Returns `None` if an axis is used twice or the mapping is a reflection.
+    pub const fn new(x: SignedAxis, y: SignedAxis, z: SignedAxis) -> Option<Self> {
+        let (a, b, c) = (x.index(), y.index(), z.index());
+        if a == b || b == c || a == c {
+            return None;
+        }
+        // Permutation parity: even permutations are rotations of (0, 1, 2)
+        let even = (a + 1) % 3 == b;
+        let negations = x.is_negative() as u8 + y.is_negative() as u8 + z.is_negative() as u8;
+        if even != (negations & 1 == 0) {
+            return None;
+        }
+        Some(Self { axes: [x, y, z] })
+    }
+
+    /// Sensor axes matching body X, Y and Z
+    pub const fn axes(&self) -> [SignedAxis; 3] {
+        self.axes
+    }
+
+    /// Transform a raw sensor frame reading into the body frame
+    pub const fn apply(&self, vector: Vector3) -> Vector3 {
+        let sensor = [vector.x, vector.y, vector.z];
+        Vector3 {
+            x: self.axes[0].pick(sensor),
+            y: self.axes[1].pick(sensor),
+            z: self.axes[2].pick(sensor),
+        }
+    }
+
+    /// Rotation matrix taking sensor frame vectors to the body frame
+    pub const fn matrix(&self) -> [[f32; 3]; 3] {
+        let mut matrix = [[0.0; 3]; 3];
+        let mut row = 0;
+        while row < 3 {
+            let axis = self.axes[row];
+            matrix[row][axis.index()] = if axis.is_negative() { -1.0 } else { 1.0 };
+            row += 1;
+        }
+        matrix
+    }
+}
+
+/// Mounting orientation of the sensor relative to the body (vehicle) frame
+///
+/// Applied by the driver to accelerometer and gyroscope readings, see
+/// [`Mpu6050::set_orientation`](crate::mpu6050::Mpu6050::set_orientation).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Orientation {
+    /// Right-angle mounting, exact on raw values
+    Axes(AxisRemap),
+    /// Arbitrary rotation, `body = matrix * sensor`. Raw values are rounded and saturated.
+    Matrix([[f32; 3]; 3]),
+}
+
+impl Orientation {
+    /// Sensor axes are the body axes
+    pub const IDENTITY: Self = Self::Axes(AxisRemap::IDENTITY);
+
+    /// Transform a raw sensor frame reading into the body frame.
+    /// The identity returns the reading unchanged, also for a [`Self::Matrix`].
+    pub fn apply(&self, vector: Vector3) -> Vector3 {
+        if self.is_identity() {
+            return vector;
+        }
+        match self {
+            Self::Axes(remap) => remap.apply(vector),
+            Self::Matrix(_) => self.apply_f32(vector.into()).map(round_to_i16),
+        }
+    }
+
+    /// Transform a scaled sensor frame vector into the body frame
+    pub fn apply_f32(&self, vector: Vector3<f32>) -> Vector3<f32> {
+        let matrix = match self {
+            Self::Axes(remap) => remap.matrix(),
+            Self::Matrix(matrix) => *matrix,
+        };
+        let row = |r: [f32; 3]| r[0] * vector.x + r[1] * vector.y + r[2] * vector.z;
+        Vector3::new(row(matrix[0]), row(matrix[1]), row(matrix[2]))
+    }
+
+    /// Check if this is the identity mapping, which [`Self::apply`] skips
+    pub fn is_identity(&self) -> bool {
+        match self {
+            Self::Axes(remap) => *remap == AxisRemap::IDENTITY,
+            Self::Matrix(matrix) => *matrix == AxisRemap::IDENTITY.matrix(),
+        }
+    }
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<AxisRemap> for Orientation {
+    fn from(remap: AxisRemap) -> Self {
+        Self::Axes(remap)
+    }
+}
+
+#[cfg(all(test, feature = "sim"))]
+mod tests {
+    use super::*;
+    use crate::compensation::Calibration;
+    use crate::mpu6050::Mpu6050;
+    use crate::sim::SimulatedMpu6050;
+
+    const AXES: [SignedAxis; 6] = [
+        SignedAxis::PosX,
+        SignedAxis::NegX,
+        SignedAxis::PosY,
+        SignedAxis::NegY,
+        SignedAxis::PosZ,
+        SignedAxis::NegZ,
+    ];
+
+    fn determinant(m: [[f32; 3]; 3]) -> f32 {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    #[test]
+    fn accepts_exactly_the_proper_rotations() {
+        let mut rotations = 0;
+        for x in AXES {
+            for y in AXES {
+                for z in AXES {
+                    let distinct =
+                        x.index() != y.index() && y.index() != z.index() && x.index() != z.index();
+                    match AxisRemap::new(x, y, z) {
+                        Some(remap) => {
+                            assert_eq!(determinant(remap.matrix()), 1.0);
+                            rotations += 1;
+                        }
+                        None if distinct => {
+                            let matrix = AxisRemap { axes: [x, y, z] }.matrix();
+                            assert_eq!(determinant(matrix), -1.0);
+                        }
+                        None => {}
+                    }
+                }
+            }
+        }
+        assert_eq!(rotations, 24);
+    }
+
+    #[test]
+    fn rejects_reflections() {
+        use SignedAxis::*;
+        // Single negation
+        assert_eq!(AxisRemap::new(NegX, PosY, PosZ), None);
+        // Odd permutation
+        assert_eq!(AxisRemap::new(PosY, PosX, PosZ), None);
+        // Odd permutation with one negation is a rotation, 90° about Z
+        let remap = AxisRemap::new(PosY, NegX, PosZ).unwrap();
+        assert_eq!(remap.apply(Vector3::new(1, 2, 3)), Vector3::new(2, -1, 3));
+        assert_eq!(AxisRemap::new(PosX, PosX, PosZ), None);
+    }
+
+    #[test]
+    fn remap_matches_matrix() {
+        let sensor = Vector3::new(100, -200, i16::MIN);
+        let remap = AxisRemap::UPSIDE_DOWN;
+        assert_eq!(remap.apply(sensor), Vector3::new(100, 200, i16::MAX));
+        let orientation = Orientation::Matrix(remap.matrix());
+        assert_eq!(
+            orientation.apply(Vector3::new(100, -200, 300)),
+            Vector3::new(100, 200, -300)
+        );
+        assert!(Orientation::Matrix(AxisRemap::IDENTITY.matrix()).is_identity());
+        assert!(!orientation.is_identity());
+    }
+
+    #[test]
+    fn driver_reads_in_body_frame() {
+        let mut sim = SimulatedMpu6050::default();
+        sim.set_accel_raw(1000, 2000, 16384);
+        sim.set_gyro_raw(10, -20, 30);
+        // Awake, PLL with X axis gyroscope reference
+        sim.poke(0x6B, 0x01);
+        sim.step(1);
+        let mut mpu = Mpu6050::new(sim, 0x68).with_orientation(AxisRemap::UPSIDE_DOWN.into());
+        assert_eq!(mpu.read_accel().unwrap(), Vector3::new(1000, -2000, -16384));
+        let sample = mpu.read_sample().unwrap();
+        assert_eq!(sample.gyro, Vector3::new(10, 20, -30));
+
+        // Offsets measured in the sensor frame follow the orientation
+        let calibration = Calibration::new().with_gyro_offset(Vector3::new(10, -20, 30));
+        let body = calibration.to_body_frame(&mpu.orientation());
+        assert_eq!(body.apply_gyro(sample.gyro), Vector3::new(0, 0, 0));
+    }
+}
@@ -3,6 +3,8 @@ use crate::config::clock_source::ClockSource;
 ///This register allows the user to configure the power mode and clock source. It also provides a bit for
 // resetting the entire device, and a bit for disabling the temperature sensor.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PwrMgmt1 {
     bits: u8,
 }
@@ -71,6 +73,36 @@ impl PwrMgmt1 {
         self.bits
     }
 
+    ///Create from register value
+    pub const fn from_register(bits: u8) -> Self {
+        Self { bits }
+    }
+
+    ///Check if device reset is requested
+    pub const fn is_device_reset(&self) -> bool {
+        (self.bits & (1 << 7)) != 0
+    }
+
+    ///Check if sleep mode is enabled
+    pub const fn is_sleep(&self) -> bool {
+        (self.bits & (1 << 6)) != 0
+    }
+
+    ///Check if cycle mode is enabled
+    pub const fn is_cycle(&self) -> bool {
+        (self.bits & (1 << 5)) != 0
+    }
+
+    ///Check if temperature sensor is disabled
+    pub const fn is_temp_sensor_disabled(&self) -> bool {
+        (self.bits & (1 << 3)) != 0
+    }
+
+    ///Get clock source, `None` if the reserved value is set
+    pub const fn clock_source(&self) -> Option<ClockSource> {
+        ClockSource::from_register(self.bits)
+    }
+
     pub const fn set_clock_source(mut self, clk_source: ClockSource) -> Self {
         self.bits &= 0xF8;
         self.bits |= clk_source as u8;
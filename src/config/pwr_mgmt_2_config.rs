@@ -0,0 +1,179 @@
+/// Wake-up frequency in Low Power Accelerometer Only (Cycle) mode, LP_WAKE_CTRL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum LpWakeCtrl {
+    /// 1.25 Hz
+    Hz1_25 = 0,
+    /// 5 Hz
+    Hz5 = 1,
+    /// 20 Hz
+    Hz20 = 2,
+    /// 40 Hz
+    Hz40 = 3,
+}
+
+/// Power Management 2 (Register 0x6C)
+///
+/// Configures the wake frequency used in Cycle mode and puts individual
+/// accelerometer and gyroscope axes into standby.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PwrMgmt2 {
+    bits: u8,
+}
+
+impl PwrMgmt2 {
+    /// Create default configuration
+    /// - LP_WAKE_CTRL: 1.25 Hz
+    /// - all axes active
+    pub const fn new() -> Self {
+        Self { bits: 0 }
+    }
+
+    /// Set wake-up frequency used in Cycle mode
+    ///
+    /// Bits 7:6 of PWR_MGMT_2 register
+    pub const fn with_lp_wake_ctrl(mut self, wake: LpWakeCtrl) -> Self {
+        self.bits &= 0x3F;
+        self.bits |= (wake as u8) << 6;
+        self
+    }
+
+    /// Put X axis accelerometer into standby
+    ///
+    /// Bit 5 of PWR_MGMT_2 register
+    pub const fn with_standby_accel_x(mut self, standby: bool) -> Self {
+        if standby {
+            self.bits |= 1 << 5;
+        } else {
+            self.bits &= !(1 << 5);
+        }
+        self
+    }
+
+    /// Put Y axis accelerometer into standby
+    ///
+    /// Bit 4 of PWR_MGMT_2 register
+    pub const fn with_standby_accel_y(mut self, standby: bool) -> Self {
+        if standby {
+            self.bits |= 1 << 4;
+        } else {
+            self.bits &= !(1 << 4);
+        }
+        self
+    }
+
+    /// Put Z axis accelerometer into standby
+    ///
+    /// Bit 3 of PWR_MGMT_2 register
+    pub const fn with_standby_accel_z(mut self, standby: bool) -> Self {
+        if standby {
+            self.bits |= 1 << 3;
+        } else {
+            self.bits &= !(1 << 3);
+        }
+        self
+    }
+
+    /// Put X axis gyroscope into standby
+    ///
+    /// Bit 2 of PWR_MGMT_2 register
+    pub const fn with_standby_gyro_x(mut self, standby: bool) -> Self {
+        if standby {
+            self.bits |= 1 << 2;
+        } else {
+            self.bits &= !(1 << 2);
+        }
+        self
+    }
+
+    /// Put Y axis gyroscope into standby
+    ///
+    /// Bit 1 of PWR_MGMT_2 register
+    pub const fn with_standby_gyro_y(mut self, standby: bool) -> Self {
+        if standby {
+            self.bits |= 1 << 1;
+        } else {
+            self.bits &= !(1 << 1);
+        }
+        self
+    }
+
+    /// Put Z axis gyroscope into standby
+    ///
+    /// Bit 0 of PWR_MGMT_2 register
+    pub const fn with_standby_gyro_z(mut self, standby: bool) -> Self {
+        if standby {
+            self.bits |= 1 << 0;
+        } else {
+            self.bits &= !(1 << 0);
+        }
+        self
+    }
+
+    /// Helper to put all gyroscope axes into standby at once
+    pub const fn with_standby_gyro_all(self, standby: bool) -> Self {
+        self.with_standby_gyro_x(standby)
+            .with_standby_gyro_y(standby)
+            .with_standby_gyro_z(standby)
+    }
+
+    /// Get register value to write to PWR_MGMT_2 register
+    pub const fn register_value(&self) -> u8 {
+        self.bits
+    }
+
+    /// Create from register value
+    pub const fn from_register(bits: u8) -> Self {
+        Self { bits }
+    }
+
+    /// Get configured wake-up frequency
+    pub const fn lp_wake_ctrl(&self) -> LpWakeCtrl {
+        match self.bits >> 6 {
+            0 => LpWakeCtrl::Hz1_25,
+            1 => LpWakeCtrl::Hz5,
+            2 => LpWakeCtrl::Hz20,
+            _ => LpWakeCtrl::Hz40,
+        }
+    }
+
+    /// Check if X axis accelerometer is in standby
+    pub const fn is_accel_x_standby(&self) -> bool {
+        (self.bits & (1 << 5)) != 0
+    }
+
+    /// Check if Y axis accelerometer is in standby
+    pub const fn is_accel_y_standby(&self) -> bool {
+        (self.bits & (1 << 4)) != 0
+    }
+
+    /// Check if Z axis accelerometer is in standby
+    pub const fn is_accel_z_standby(&self) -> bool {
+        (self.bits & (1 << 3)) != 0
+    }
+
+    /// Check if X axis gyroscope is in standby
+    pub const fn is_gyro_x_standby(&self) -> bool {
+        (self.bits & (1 << 2)) != 0
+    }
+
+    /// Check if Y axis gyroscope is in standby
+    pub const fn is_gyro_y_standby(&self) -> bool {
+        (self.bits & (1 << 1)) != 0
+    }
+
+    /// Check if Z axis gyroscope is in standby
+    pub const fn is_gyro_z_standby(&self) -> bool {
+        (self.bits & (1 << 0)) != 0
+    }
+}
+
+impl Default for PwrMgmt2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,293 @@
+//! Interrupt driven sample reading
+//!
+//! Instead of polling, [`DataReadyReader`] waits on the GPIO wired to the INT pin. The INT pin
+//! is configured as latched, so a sample that became ready before the wait started is not
+//! missed: the pin stays at its active level until INT_STATUS is read.
+
+use core::fmt;
+use core::future::{poll_fn, Future};
+use core::pin::{pin, Pin};
+use core::task::Poll;
+
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+
+use crate::bus::RegisterBus;
+use crate::config::{IntPinConfig, InterruptEnable};
+use crate::errors::MPU6050Error;
+use crate::mpu6050::{Mpu6050, Sample, TransitionError};
+use crate::registers::Registers;
+
+/// DATA_RDY_INT bit of INT_STATUS
+const INT_STATUS_DATA_RDY: u8 = 1 << 0;
+
+/// Error returned by [`DataReadyReader`]
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub enum DataReadyError<E, P> {
+    /// Communication with the device failed
+    Device(MPU6050Error<E>),
+    /// Waiting on the interrupt GPIO failed
+    Pin(P),
+    /// No data ready interrupt within the timeout
+    Timeout,
+}
+
+impl<E, P> From<MPU6050Error<E>> for DataReadyError<E, P> {
+    fn from(error: MPU6050Error<E>) -> Self {
+        Self::Device(error)
+    }
+}
+
+impl<E: fmt::Debug, P: fmt::Debug> fmt::Display for DataReadyError<E, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Device(e) => write!(f, "{}", e),
+            Self::Pin(e) => write!(f, "Interrupt pin error: {:?}", e),
+            Self::Timeout => write!(f, "Timed out waiting for data ready interrupt"),
+        }
+    }
+}
+
+/// Driver, interrupt GPIO and delay used by a [`DataReadyReader`]
+type Parts<BUS, P, D> = (Mpu6050<BUS>, P, D);
+
+/// Setup failure, hands the parts back to the caller
+type SetupError<BUS, P, D> = TransitionError<Parts<BUS, P, D>, <BUS as RegisterBus>::Error>;
+
+/// Reads samples when the data ready interrupt fires
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct DataReadyReader<BUS, P, D> {
+    mpu: Mpu6050<BUS>,
+    pin: P,
+    delay: D,
+    active_low: bool,
+    timeout_us: u32,
+}
+
+impl<BUS, P, D> DataReadyReader<BUS, P, D>
+where
+    BUS: RegisterBus,
+    P: Wait,
+    D: DelayNs,
+{
+    /// Configure the data ready interrupt and take over the device, the interrupt GPIO and a
+    /// delay used for timeouts.
+    ///
+    /// Level and output mode are taken from `int_pin`. Latching is always enabled and
+    /// INT_RD_CLEAR disabled, so only the INT_STATUS read in [`Self::wait_for_sample`] clears
+    /// the interrupt. The data ready interrupt is added to the interrupts already enabled.
+    /// Samples are expected at least every `timeout_us` microseconds.
+    ///
+    /// On error the driver, the GPIO and the delay are handed back in
+    /// [`TransitionError::device`].
+    pub fn new(
+        mut mpu: Mpu6050<BUS>,
+        pin: P,
+        delay: D,
+        int_pin: IntPinConfig,
+        timeout_us: u32,
+    ) -> Result<Self, SetupError<BUS, P, D>> {
+        let int_pin = int_pin.with_latch_int(true).with_int_rd_clear(false);
+        if let Err(error) = Self::configure(&mut mpu, int_pin) {
+            return Err(TransitionError {
+                device: (mpu, pin, delay),
+                error,
+            });
+        }
+
+        Ok(Self {
+            mpu,
+            pin,
+            delay,
+            active_low: int_pin.is_int_active_low(),
+            timeout_us,
+        })
+    }
+
+    fn configure(
+        mpu: &mut Mpu6050<BUS>,
+        int_pin: IntPinConfig,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        mpu.configure_interrupt_pin(int_pin)?;
+
+        let int_enable =
+            InterruptEnable::from_register(mpu.read_register(Registers::InterruptEnable)?);
+        mpu.configure_interrupt_enable(int_enable.with_data_ready(true))?;
+        // Clear a stale interrupt so the first wait is for a fresh sample
+        mpu.read_register(Registers::InterruptStatus)?;
+        Ok(())
+    }
+
+    /// Wait for the next data ready interrupt, clear it and burst read the sample.
+    ///
+    /// Other enabled interrupts also assert the INT pin. They are cleared along with it and
+    /// waiting continues until DATA_RDY is set. The timeout covers the whole call, so a pin
+    /// stuck at its active level still ends in [`DataReadyError::Timeout`].
+    pub async fn wait_for_sample(
+        &mut self,
+    ) -> Result<Sample, DataReadyError<BUS::Error, P::Error>> {
+        let mut timeout = pin!(self.delay.delay_us(self.timeout_us));
+        loop {
+            let active = if self.active_low {
+                with_timeout(self.pin.wait_for_low(), timeout.as_mut()).await
+            } else {
+                with_timeout(self.pin.wait_for_high(), timeout.as_mut()).await
+            };
+            match active {
+                Some(Ok(())) => {}
+                Some(Err(e)) => return Err(DataReadyError::Pin(e)),
+                None => return Err(DataReadyError::Timeout),
+            }
+
+            let status = self.mpu.read_register(Registers::InterruptStatus)?;
+            if status & INT_STATUS_DATA_RDY != 0 {
+                return Ok(self.mpu.read_sample()?);
+            }
+        }
+    }
+
+    /// Change how long [`Self::wait_for_sample`] waits for an interrupt
+    pub fn set_timeout_us(&mut self, timeout_us: u32) {
+        self.timeout_us = timeout_us;
+    }
+
+    /// Access the driver, e.g. to change the configuration between samples
+    pub fn mpu(&mut self) -> &mut Mpu6050<BUS> {
+        &mut self.mpu
+    }
+
+    /// Disable the data ready interrupt and return the driver, the GPIO and the delay.
+    ///
+    /// On error the reader is handed back in [`TransitionError::device`].
+    pub fn release(mut self) -> Result<Parts<BUS, P, D>, TransitionError<Self, BUS::Error>> {
+        let disabled = self
+            .mpu
+            .read_register(Registers::InterruptEnable)
+            .and_then(|value| {
+                let int_enable = InterruptEnable::from_register(value).with_data_ready(false);
+                self.mpu.configure_interrupt_enable(int_enable)
+            });
+        match disabled {
+            Ok(()) => Ok((self.mpu, self.pin, self.delay)),
+            Err(error) => Err(TransitionError {
+                device: self,
+                error,
+            }),
+        }
+    }
+}
+
+/// Run `future` until it completes, or return `None` once `timeout` has elapsed.
+/// `timeout` is checked first and must not be polled again after it returned `None`.
+async fn with_timeout<F: Future>(
+    future: F,
+    mut timeout: Pin<&mut impl Future<Output = ()>>,
+) -> Option<F::Output> {
+    let mut future = pin!(future);
+    poll_fn(|cx| {
+        if timeout.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+        future.as_mut().poll(cx).map(Some)
+    })
+    .await
+}
+
+#[cfg(all(test, feature = "sim"))]
+mod tests {
+    use core::convert::Infallible;
+
+    use super::*;
+    use crate::sim::{Fault, FaultRule, FaultyI2c, SimulatedMpu6050};
+
+    struct NoPin;
+
+    impl embedded_hal::digital::ErrorType for NoPin {
+        type Error = Infallible;
+    }
+
+    impl Wait for NoPin {
+        async fn wait_for_high(&mut self) -> Result<(), Infallible> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), Infallible> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Infallible> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Infallible> {
+            core::future::pending().await
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), Infallible> {
+            core::future::pending().await
+        }
+    }
+
+    struct NoDelay;
+
+    impl DelayNs for NoDelay {
+        async fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    const INT_ENABLE: u8 = 0x38;
+
+    fn nak_rule(register: u8) -> FaultRule {
+        FaultRule::new(Fault::Nak).on_register(register).times(1)
+    }
+
+    #[test]
+    fn new_returns_parts_on_error() {
+        let mut i2c = FaultyI2c::new(SimulatedMpu6050::default());
+        i2c.inject(nak_rule(INT_ENABLE)).unwrap();
+        let mpu = Mpu6050::new(i2c, 0x68);
+
+        let error = DataReadyReader::new(mpu, NoPin, NoDelay, IntPinConfig::new(), 1000)
+            .err()
+            .unwrap();
+        assert_eq!(error.error.register(), Some(INT_ENABLE));
+
+        // The rule fired once, a retry with the returned parts succeeds
+        let (mpu, pin, delay) = error.device;
+        let reader = DataReadyReader::new(mpu, pin, delay, IntPinConfig::new(), 1000)
+            .ok()
+            .unwrap();
+        let (mpu, _, _) = reader.release().ok().unwrap();
+        assert_eq!(mpu.release().inner_mut().peek(INT_ENABLE), 0);
+    }
+
+    #[test]
+    fn new_enables_latched_data_ready() {
+        let mpu = Mpu6050::new(SimulatedMpu6050::default(), 0x68);
+        let reader = DataReadyReader::new(mpu, NoPin, NoDelay, IntPinConfig::new(), 1000)
+            .ok()
+            .unwrap();
+        let (mpu, _, _) = reader.release().ok().unwrap();
+        let sim = mpu.release();
+        assert_eq!(sim.peek(INT_ENABLE), 0);
+        // LATCH_INT_EN set, INT_RD_CLEAR cleared
+        assert_eq!(sim.peek(0x37) & 0x30, 0x20);
+    }
+
+    #[test]
+    fn release_returns_reader_on_error() {
+        let mut i2c = FaultyI2c::new(SimulatedMpu6050::default());
+        // INT_ENABLE is read and written once by new, the next access is in release
+        i2c.inject(nak_rule(INT_ENABLE).after(2)).unwrap();
+        let mpu = Mpu6050::new(i2c, 0x68);
+        let reader = DataReadyReader::new(mpu, NoPin, NoDelay, IntPinConfig::new(), 1000)
+            .ok()
+            .unwrap();
+
+        let error = reader.release().err().unwrap();
+        assert_eq!(error.error.register(), Some(INT_ENABLE));
+        let (mpu, _, _) = error.device.release().ok().unwrap();
+        assert_eq!(mpu.release().inner_mut().peek(INT_ENABLE), 0);
+    }
+}
@@ -0,0 +1,3 @@
+
+pub mod register_dump;
+pub use register_dump::{DecodedRegister, RegisterChange, RegisterDump};
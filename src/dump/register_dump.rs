@@ -0,0 +1,321 @@
+use core::fmt;
+
+use crate::bus::RegisterBus;
+use crate::config::{
+    AccelSettings, DlpfConfig, ExtSync, FifoConfig, GyroConfig, IntPinConfig, InterruptEnable,
+    PwrMgmt1, PwrMgmt2,
+};
+use crate::errors::MPU6050Error;
+use crate::mpu6050::Mpu6050;
+use crate::registers::Registers;
+
+/// First register included in a dump (SELF_TEST_X)
+pub const DUMP_START: u8 = 0x0D;
+/// Last register included in a dump (WHO_AM_I)
+pub const DUMP_END: u8 = 0x75;
+/// Number of bytes in a dump
+pub const DUMP_LEN: usize = (DUMP_END - DUMP_START + 1) as usize;
+
+/// Snapshot of the register map from 0x0D to 0x75
+///
+/// `Display` renders a report with every named register and its decoded bitfields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct RegisterDump {
+    values: [u8; DUMP_LEN],
+}
+
+impl RegisterDump {
+    /// Create from raw register values, starting at [`DUMP_START`]
+    pub const fn from_bytes(values: [u8; DUMP_LEN]) -> Self {
+        Self { values }
+    }
+
+    /// Get raw register values, starting at [`DUMP_START`]
+    pub const fn as_bytes(&self) -> &[u8; DUMP_LEN] {
+        &self.values
+    }
+
+    /// Get value of the register at `address`, `None` if it is outside the dump
+    pub fn get(&self, address: u8) -> Option<u8> {
+        let index = address.checked_sub(DUMP_START)? as usize;
+        self.values.get(index).copied()
+    }
+
+    /// Iterate over all named registers
+    pub fn registers(&self) -> impl Iterator<Item = DecodedRegister> + '_ {
+        (DUMP_START..=DUMP_END)
+            .zip(self.values.iter())
+            .filter(|(address, _)| Registers::from_address(*address).is_some())
+            .map(|(address, &value)| DecodedRegister { address, value })
+    }
+
+    /// Iterate over registers whose value differs between `self` and `after`
+    pub fn diff<'a>(&'a self, after: &'a Self) -> impl Iterator<Item = RegisterChange> + 'a {
+        (DUMP_START..=DUMP_END)
+            .zip(self.values.iter().zip(after.values.iter()))
+            .filter(|(_, (before, after))| before != after)
+            .map(|(address, (&before, &after))| RegisterChange {
+                address,
+                before,
+                after,
+            })
+    }
+}
+
+impl fmt::Display for RegisterDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for register in self.registers() {
+            writeln!(f, "{}", register)?;
+        }
+        Ok(())
+    }
+}
+
+/// Single register value labelled with its name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct DecodedRegister {
+    pub address: u8,
+    pub value: u8,
+}
+
+impl DecodedRegister {
+    /// Register name as used in the datasheet, `None` for unnamed addresses
+    pub fn name(&self) -> Option<&'static str> {
+        Registers::from_address(self.address).map(|register| register.name())
+    }
+}
+
+impl fmt::Display for DecodedRegister {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "0x{:02X} {:<18} 0x{:02X}",
+            self.address,
+            self.name().unwrap_or("-"),
+            self.value
+        )?;
+        if let Some(register) = Registers::from_address(self.address) {
+            write_fields(f, register, self.value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Register that changed between two dumps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct RegisterChange {
+    pub address: u8,
+    pub before: u8,
+    pub after: u8,
+}
+
+impl fmt::Display for RegisterChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = Registers::from_address(self.address).map_or("-", |register| register.name());
+        write!(
+            f,
+            "0x{:02X} {:<18} 0x{:02X} -> 0x{:02X}",
+            self.address, name, self.before, self.after
+        )?;
+        if let Some(register) = Registers::from_address(self.address) {
+            write_fields(f, register, self.after)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write names of the set flags, or `-` if none is set
+fn write_flags(f: &mut fmt::Formatter<'_>, flags: &[(bool, &str)]) -> fmt::Result {
+    write!(f, " ")?;
+    let mut any = false;
+    for (_, name) in flags.iter().filter(|(set, _)| *set) {
+        write!(f, " {}", name)?;
+        any = true;
+    }
+    if !any {
+        write!(f, " -")?;
+    }
+    Ok(())
+}
+
+/// Render the bitfields of a register value
+fn write_fields(f: &mut fmt::Formatter<'_>, register: Registers, value: u8) -> fmt::Result {
+    let bit = |n: u8| value & (1 << n) != 0;
+    match register {
+        Registers::SelfTestX | Registers::SelfTestY | Registers::SelfTestZ => write!(
+            f,
+            "  A_TEST[4:2]={} G_TEST={}",
+            value >> 5,
+            value & 0x1F
+        ),
+        Registers::SelfTestA => write!(
+            f,
+            "  XA_TEST[1:0]={} YA_TEST[1:0]={} ZA_TEST[1:0]={}",
+            (value >> 4) & 0x03,
+            (value >> 2) & 0x03,
+            value & 0x03
+        ),
+        Registers::SmprtDiv => write!(f, "  divider={}", value),
+        Registers::Config => {
+            write!(f, "  EXT_SYNC_SET={:?}", ExtSync::from_register(value))?;
+            match DlpfConfig::from_register(value) {
+                Some(dlpf) => write!(
+                    f,
+                    " DLPF_CFG={:?} (accel {} Hz, gyro {} Hz)",
+                    dlpf,
+                    dlpf.get_accel_bandwidth_hz(),
+                    dlpf.get_gyro_bandwidth_hz()
+                ),
+                None => write!(f, " DLPF_CFG=reserved"),
+            }
+        }
+        Registers::GyroConfig => {
+            let range = GyroConfig::from_register(value);
+            write!(f, "  FS_SEL=±{} °/s", range.scale_range())?;
+            write_flags(f, &[(bit(7), "XG_ST"), (bit(6), "YG_ST"), (bit(5), "ZG_ST")])
+        }
+        Registers::AccelConfig => {
+            let settings = AccelSettings::from_register(value);
+            write!(f, "  AFS_SEL=±{} g", settings.range().scale_range())?;
+            match settings.hpf() {
+                Some(hpf) => write!(f, " ACCEL_HPF={:?}", hpf)?,
+                None => write!(f, " ACCEL_HPF=reserved")?,
+            }
+            write_flags(f, &[(bit(7), "XA_ST"), (bit(6), "YA_ST"), (bit(5), "ZA_ST")])
+        }
+        Registers::MotThr => write!(f, "  threshold={}", value),
+        Registers::FifoEn => {
+            let fifo = FifoConfig::from_register(value);
+            write_flags(
+                f,
+                &[
+                    (fifo.is_temp_enabled(), "TEMP"),
+                    (fifo.is_gyro_x_enabled(), "XG"),
+                    (fifo.is_gyro_y_enabled(), "YG"),
+                    (fifo.is_gyro_z_enabled(), "ZG"),
+                    (fifo.is_accel_enabled(), "ACCEL"),
+                    (fifo.is_slave2_enabled(), "SLV2"),
+                    (fifo.is_slave1_enabled(), "SLV1"),
+                    (fifo.is_slave0_enabled(), "SLV0"),
+                ],
+            )
+        }
+        Registers::I2cMstStatus => write_flags(
+            f,
+            &[
+                (bit(7), "PASS_THROUGH"),
+                (bit(6), "I2C_SLV4_DONE"),
+                (bit(5), "I2C_LOST_ARB"),
+                (bit(4), "I2C_SLV4_NACK"),
+                (bit(3), "I2C_SLV3_NACK"),
+                (bit(2), "I2C_SLV2_NACK"),
+                (bit(1), "I2C_SLV1_NACK"),
+                (bit(0), "I2C_SLV0_NACK"),
+            ],
+        ),
+        Registers::InterruptPinCfg => {
+            let pin = IntPinConfig::from_register(value);
+            write_flags(
+                f,
+                &[
+                    (pin.is_int_active_low(), "INT_LEVEL(active low)"),
+                    (pin.is_int_open_drain(), "INT_OPEN"),
+                    (pin.is_latch_enabled(), "LATCH_INT_EN"),
+                    (pin.is_clear_on_any_read(), "INT_RD_CLEAR"),
+                    (pin.is_fsync_active_low(), "FSYNC_INT_LEVEL(active low)"),
+                    (pin.is_fsync_int_enabled(), "FSYNC_INT_EN"),
+                    (pin.is_i2c_bypass_enabled(), "I2C_BYPASS_EN"),
+                ],
+            )
+        }
+        Registers::InterruptEnable => {
+            let enable = InterruptEnable::from_register(value);
+            write_flags(
+                f,
+                &[
+                    (enable.has_motion_detection(), "MOT_EN"),
+                    (enable.has_fifo_overflow(), "FIFO_OFLOW_EN"),
+                    (enable.has_i2c_master(), "I2C_MST_INT_EN"),
+                    (enable.has_data_ready(), "DATA_RDY_EN"),
+                ],
+            )
+        }
+        Registers::InterruptStatus => write_flags(
+            f,
+            &[
+                (bit(6), "MOT_INT"),
+                (bit(4), "FIFO_OFLOW_INT"),
+                (bit(3), "I2C_MST_INT"),
+                (bit(0), "DATA_RDY_INT"),
+            ],
+        ),
+        Registers::SignalPathReset => write_flags(
+            f,
+            &[(bit(2), "GYRO_RESET"), (bit(1), "ACCEL_RESET"), (bit(0), "TEMP_RESET")],
+        ),
+        Registers::UserCtrl => write_flags(
+            f,
+            &[
+                (bit(6), "FIFO_EN"),
+                (bit(5), "I2C_MST_EN"),
+                (bit(4), "I2C_IF_DIS"),
+                (bit(2), "FIFO_RESET"),
+                (bit(1), "I2C_MST_RESET"),
+                (bit(0), "SIG_COND_RESET"),
+            ],
+        ),
+        Registers::PowerMgmt1 => {
+            let pwr = PwrMgmt1::from_register(value);
+            match pwr.clock_source() {
+                Some(clock) => write!(f, "  CLKSEL={:?}", clock)?,
+                None => write!(f, "  CLKSEL=reserved")?,
+            }
+            write_flags(
+                f,
+                &[
+                    (pwr.is_device_reset(), "DEVICE_RESET"),
+                    (pwr.is_sleep(), "SLEEP"),
+                    (pwr.is_cycle(), "CYCLE"),
+                    (pwr.is_temp_sensor_disabled(), "TEMP_DIS"),
+                ],
+            )
+        }
+        Registers::PowerMgmt2 => {
+            let pwr = PwrMgmt2::from_register(value);
+            write!(f, "  LP_WAKE_CTRL={:?}", pwr.lp_wake_ctrl())?;
+            write_flags(
+                f,
+                &[
+                    (pwr.is_accel_x_standby(), "STBY_XA"),
+                    (pwr.is_accel_y_standby(), "STBY_YA"),
+                    (pwr.is_accel_z_standby(), "STBY_ZA"),
+                    (pwr.is_gyro_x_standby(), "STBY_XG"),
+                    (pwr.is_gyro_y_standby(), "STBY_YG"),
+                    (pwr.is_gyro_z_standby(), "STBY_ZG"),
+                ],
+            )
+        }
+        Registers::FifoRW => write!(f, "  (not read)"),
+        _ => Ok(()),
+    }
+}
+
+impl<BUS> Mpu6050<BUS>
+where
+    BUS: RegisterBus,
+{
+    /// Read the full register map from 0x0D to 0x75.
+    ///
+    /// FIFO_R_W is skipped (reported as 0) because reading it would consume FIFO data.
+    /// Note that reading INT_STATUS clears the interrupt status bits.
+    pub fn dump_registers(&mut self) -> Result<RegisterDump, MPU6050Error<BUS::Error>> {
+        let mut values = [0u8; DUMP_LEN];
+        let fifo_rw = (Registers::FifoRW.get_register_address() - DUMP_START) as usize;
+        self.read_registers(DUMP_START, &mut values[..fifo_rw])?;
+        values[DUMP_LEN - 1] = self.read_register(Registers::WhoAmI)?;
+        Ok(RegisterDump::from_bytes(values))
+    }
+}
@@ -0,0 +1,4 @@
+pub mod sampler;
+pub mod sink;
+pub use sampler::{Sampler, SamplerStats, SharedStats, TimestampedSample};
+pub use sink::{Delivery, SampleSink};
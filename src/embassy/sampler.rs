@@ -0,0 +1,174 @@
+//! Sampling loop for Embassy
+//!
+//! [`Sampler`] waits for the data ready interrupt, reads and timestamps each sample and hands
+//! it to a [`SampleSink`], an `embassy_sync` channel or pub-sub publisher. Embassy tasks can't
+//! be generic, so the loop is run from a task in the application:
+//!
+//! ```ignore
+//! static SAMPLES: PubSubChannel<CriticalSectionRawMutex, TimestampedSample, 8, 3, 1> =
+//!     PubSubChannel::new();
+//! static STATS: SharedStats<CriticalSectionRawMutex> = SharedStats::new();
+//!
+//! #[embassy_executor::task]
+//! async fn imu_task(reader: DataReadyReader<I2c0, Input<'static>, Delay>) -> ! {
+//!     let publisher = SAMPLES.publisher().unwrap();
+//!     Sampler::new(reader, publisher, &STATS).run().await
+//! }
+//! ```
+
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_time::Instant;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+
+use crate::bus::RegisterBus;
+use crate::data_ready::{DataReadyError, DataReadyReader};
+use crate::embassy::{Delivery, SampleSink};
+use crate::mpu6050::Sample;
+
+/// Sample with the time its data ready interrupt was handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimestampedSample {
+    /// `embassy_time::Instant` in microseconds since boot
+    pub timestamp_us: u64,
+    pub sample: Sample,
+}
+
+/// Counters kept by [`Sampler`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SamplerStats {
+    /// Samples read from the device
+    pub samples: u32,
+    /// Samples that overwrote the oldest queued sample, see [`Delivery::Overwritten`]
+    pub overwritten: u32,
+    /// Samples discarded because the queue was full, see [`Delivery::Dropped`]
+    pub dropped: u32,
+    /// Failed waits or reads, including timeouts
+    pub errors: u32,
+    /// Highest number of queued messages seen after delivering a sample
+    pub max_queue_len: usize,
+}
+
+/// [`SamplerStats`] shared between the sampling task and other tasks
+pub struct SharedStats<M: RawMutex> {
+    stats: Mutex<M, Cell<SamplerStats>>,
+}
+
+impl<M: RawMutex> SharedStats<M> {
+    pub const fn new() -> Self {
+        Self {
+            stats: Mutex::new(Cell::new(SamplerStats {
+                samples: 0,
+                overwritten: 0,
+                dropped: 0,
+                errors: 0,
+                max_queue_len: 0,
+            })),
+        }
+    }
+
+    /// Get a copy of the current counters
+    pub fn get(&self) -> SamplerStats {
+        self.stats.lock(Cell::get)
+    }
+
+    /// Reset all counters to zero
+    pub fn reset(&self) {
+        self.stats.lock(|stats| stats.set(SamplerStats::default()));
+    }
+
+    fn update(&self, update: impl FnOnce(&mut SamplerStats)) {
+        self.stats.lock(|cell| {
+            let mut stats = cell.get();
+            update(&mut stats);
+            cell.set(stats);
+        });
+    }
+}
+
+impl<M: RawMutex> Default for SharedStats<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads samples on data ready and publishes them, see the [module documentation](self)
+pub struct Sampler<'a, BUS, P, D, S, M: RawMutex> {
+    reader: DataReadyReader<BUS, P, D>,
+    sink: S,
+    stats: &'a SharedStats<M>,
+}
+
+impl<'a, BUS, P, D, S, M> Sampler<'a, BUS, P, D, S, M>
+where
+    BUS: RegisterBus,
+    P: Wait,
+    D: DelayNs,
+    S: SampleSink,
+    M: RawMutex,
+{
+    /// Create sampler publishing the samples of `reader` into `sink`
+    pub fn new(reader: DataReadyReader<BUS, P, D>, sink: S, stats: &'a SharedStats<M>) -> Self {
+        Self {
+            reader,
+            sink,
+            stats,
+        }
+    }
+
+    /// Wait for the next sample, timestamp it and hand it to the sink
+    pub async fn sample_once(
+        &mut self,
+    ) -> Result<TimestampedSample, DataReadyError<BUS::Error, P::Error>> {
+        let sample = match self.reader.wait_for_sample().await {
+            Ok(sample) => sample,
+            Err(error) => {
+                self.stats
+                    .update(|stats| stats.errors = stats.errors.wrapping_add(1));
+                return Err(error);
+            }
+        };
+        let sample = TimestampedSample {
+            timestamp_us: Instant::now().as_micros(),
+            sample,
+        };
+
+        let delivery = self.sink.deliver(sample);
+        let queue_len = self.sink.queue_len();
+        self.stats.update(|stats| {
+            stats.samples = stats.samples.wrapping_add(1);
+            match delivery {
+                Delivery::Queued => {}
+                Delivery::Overwritten => stats.overwritten = stats.overwritten.wrapping_add(1),
+                Delivery::Dropped => stats.dropped = stats.dropped.wrapping_add(1),
+            }
+            stats.max_queue_len = stats.max_queue_len.max(queue_len);
+        });
+        Ok(sample)
+    }
+
+    /// Sample forever. Errors are counted in [`SamplerStats::errors`] and sampling continues.
+    pub async fn run(&mut self) -> ! {
+        loop {
+            // Already counted, the next wait starts over
+            let _ = self.sample_once().await;
+        }
+    }
+
+    /// Access the reader, e.g. to reconfigure the device between samples
+    pub fn reader(&mut self) -> &mut DataReadyReader<BUS, P, D> {
+        &mut self.reader
+    }
+
+    /// Stop sampling and return the reader and the sink
+    pub fn release(self) -> (DataReadyReader<BUS, P, D>, S) {
+        (self.reader, self.sink)
+    }
+}
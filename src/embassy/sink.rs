@@ -0,0 +1,91 @@
+//! Destinations for samples produced by [`Sampler`](crate::embassy::Sampler)
+
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::channel::Sender;
+use embassy_sync::pubsub::{ImmediatePublisher, Publisher};
+
+use crate::embassy::TimestampedSample;
+
+/// What happened to a sample handed to a [`SampleSink`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub enum Delivery {
+    /// Queued for all consumers
+    Queued,
+    /// Queued, but the oldest message was discarded for consumers that fell behind.
+    /// Pub-sub subscribers see this as `WaitResult::Lagged`.
+    Overwritten,
+    /// The queue was full, the sample was discarded
+    Dropped,
+}
+
+/// Non-blocking destination for samples
+///
+/// The sampler never waits for consumers, a full queue would otherwise stall reading and the
+/// samples would be lost in the device instead.
+pub trait SampleSink {
+    /// Hand over a sample
+    fn deliver(&mut self, sample: TimestampedSample) -> Delivery;
+
+    /// Number of messages currently queued
+    fn queue_len(&self) -> usize;
+}
+
+/// Channel with a single consumer, samples are dropped while it is full
+impl<M, const N: usize> SampleSink for Sender<'_, M, TimestampedSample, N>
+where
+    M: RawMutex,
+{
+    fn deliver(&mut self, sample: TimestampedSample) -> Delivery {
+        match self.try_send(sample) {
+            Ok(()) => Delivery::Queued,
+            Err(_) => Delivery::Dropped,
+        }
+    }
+
+    fn queue_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Pub-sub channel, the oldest sample is overwritten while it is full
+impl<M, const CAP: usize, const SUBS: usize, const PUBS: usize> SampleSink
+    for Publisher<'_, M, TimestampedSample, CAP, SUBS, PUBS>
+where
+    M: RawMutex,
+{
+    fn deliver(&mut self, sample: TimestampedSample) -> Delivery {
+        match self.try_publish(sample) {
+            Ok(()) => Delivery::Queued,
+            Err(sample) => {
+                self.publish_immediate(sample);
+                Delivery::Overwritten
+            }
+        }
+    }
+
+    fn queue_len(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Pub-sub channel without a publisher slot, the oldest sample is overwritten while it is full
+impl<M, const CAP: usize, const SUBS: usize, const PUBS: usize> SampleSink
+    for ImmediatePublisher<'_, M, TimestampedSample, CAP, SUBS, PUBS>
+where
+    M: RawMutex,
+{
+    fn deliver(&mut self, sample: TimestampedSample) -> Delivery {
+        match self.try_publish(sample) {
+            Ok(()) => Delivery::Queued,
+            Err(sample) => {
+                self.publish_immediate(sample);
+                Delivery::Overwritten
+            }
+        }
+    }
+
+    fn queue_len(&self) -> usize {
+        self.len()
+    }
+}
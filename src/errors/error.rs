@@ -1,9 +1,145 @@
+// `I2CError` stays matchable here until it is removed
+#![allow(deprecated)]
+
 use core::{error::Error, fmt};
 
+use embedded_hal::i2c::ErrorKind;
+
+/// Driver error
+///
+/// Every variant besides [`Self::I2CError`] and [`Self::AddressOutOfScope`] carries the address
+/// of the register involved.
 #[derive(Debug)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
 pub enum MPU6050Error<E> {
+    /// Bus error without register context, only created by the `From<E>` conversion
+    #[deprecated(note = "bus errors are reported as `RegisterAccess`, which names the register")]
     I2CError(E),
-    AddressOutOfScope
+    AddressOutOfScope,
+    /// Bus transfer to or from `register` failed, on I2C or SPI
+    RegisterAccess { register: u8, error: E },
+    /// WHO_AM_I returned an unexpected value, the device is not an MPU-6050 or the address is
+    /// wrong
+    WrongDeviceId { register: u8, expected: u8, actual: u8 },
+    /// `register` did not reach the expected state in time
+    Timeout { register: u8 },
+    /// The FIFO overflowed and the oldest data was lost, reported through `register` (INT_STATUS)
+    FifoOverflow { register: u8 },
+    /// The value for `register` is not valid or contradicts other settings
+    InvalidConfig { register: u8, value: u8 },
+    /// Self-test response out of tolerance on the axis of `register` (SELF_TEST_X/Y/Z)
+    SelfTestFailed { register: u8 },
+    /// The detected chip variant lacks the feature controlled by `register`
+    UnsupportedFeature { register: u8 },
+    /// Register read back a different value than was written
+    VerificationMismatch {
+        register: u8,
+        expected: u8,
+        actual: u8,
+    },
+}
+
+impl<E> MPU6050Error<E> {
+    /// Address of the register involved, if known
+    pub const fn register(&self) -> Option<u8> {
+        match self {
+            Self::I2CError(_) | Self::AddressOutOfScope => None,
+            Self::RegisterAccess { register, .. }
+            | Self::WrongDeviceId { register, .. }
+            | Self::Timeout { register }
+            | Self::FifoOverflow { register }
+            | Self::InvalidConfig { register, .. }
+            | Self::SelfTestFailed { register }
+            | Self::UnsupportedFeature { register }
+            | Self::VerificationMismatch { register, .. } => Some(*register),
+        }
+    }
+}
+
+impl<E> MPU6050Error<E> {
+    /// Check whether repeating the failed operation may succeed, with `is_transient`
+    /// classifying the bus error. For buses other than I2C and SPI.
+    ///
+    /// Timeouts and verification mismatches, which may be caused by a corrupted read, are
+    /// retryable. A wrong device, an invalid configuration, a failed self-test, an unsupported
+    /// feature or a FIFO overflow, which needs a FIFO reset first, are not.
+    pub fn is_retryable_with(&self, is_transient: impl FnOnce(&E) -> bool) -> bool {
+        match self {
+            Self::I2CError(e) | Self::RegisterAccess { error: e, .. } => is_transient(e),
+            Self::Timeout { .. } | Self::VerificationMismatch { .. } => true,
+            Self::AddressOutOfScope
+            | Self::WrongDeviceId { .. }
+            | Self::FifoOverflow { .. }
+            | Self::InvalidConfig { .. }
+            | Self::SelfTestFailed { .. }
+            | Self::UnsupportedFeature { .. } => false,
+        }
+    }
+}
+
+impl<E> MPU6050Error<E>
+where
+    E: embedded_hal::i2c::Error,
+{
+    /// Check whether repeating the failed operation may succeed, see [`Self::is_retryable_with`].
+    ///
+    /// Arbitration loss, NACK, bus errors and overruns are transient I2C errors.
+    pub fn is_retryable(&self) -> bool {
+        self.is_retryable_with(|e| {
+            matches!(
+                e.kind(),
+                ErrorKind::ArbitrationLoss
+                    | ErrorKind::NoAcknowledge(_)
+                    | ErrorKind::Bus
+                    | ErrorKind::Overrun
+            )
+        })
+    }
+}
+
+impl<E> MPU6050Error<E>
+where
+    E: embedded_hal::spi::Error,
+{
+    /// Check whether repeating the failed operation may succeed on SPI, see
+    /// [`Self::is_retryable_with`].
+    ///
+    /// Overruns and mode faults, i.e. another master driving the bus, are transient SPI
+    /// errors. Frame format and chip select faults point to a wrong bus setup.
+    pub fn is_retryable_spi(&self) -> bool {
+        self.is_retryable_with(|e| {
+            matches!(
+                e.kind(),
+                embedded_hal::spi::ErrorKind::Overrun | embedded_hal::spi::ErrorKind::ModeFault
+            )
+        })
+    }
+}
+
+/// Bus errors keep their kind, all other errors map to [`ErrorKind::Other`]
+impl<E> embedded_hal::i2c::Error for MPU6050Error<E>
+where
+    E: embedded_hal::i2c::Error,
+{
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::I2CError(e) | Self::RegisterAccess { error: e, .. } => e.kind(),
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// Same mapping as the I2C implementation, for drivers on SPI
+impl<E> embedded_hal::spi::Error for MPU6050Error<E>
+where
+    E: embedded_hal::spi::Error,
+{
+    fn kind(&self) -> embedded_hal::spi::ErrorKind {
+        match self {
+            Self::I2CError(e) | Self::RegisterAccess { error: e, .. } => e.kind(),
+            _ => embedded_hal::spi::ErrorKind::Other,
+        }
+    }
 }
 
 impl<E> fmt::Display for MPU6050Error<E>
@@ -12,8 +148,46 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::I2CError(e) => write!(f, "I2c error: {:?}", e),
-            Self::AddressOutOfScope => write!(f, "Given address out of scope")
+            Self::I2CError(e) => write!(f, "Bus error: {:?}", e),
+            Self::AddressOutOfScope => write!(f, "Given address out of scope"),
+            Self::RegisterAccess { register, error } => {
+                write!(f, "Bus error accessing register 0x{:02X}: {:?}", register, error)
+            }
+            Self::WrongDeviceId {
+                register,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Register 0x{:02X} device id mismatch: expected 0x{:02X}, read 0x{:02X}",
+                register, expected, actual
+            ),
+            Self::Timeout { register } => write!(f, "Register 0x{:02X} timed out", register),
+            Self::FifoOverflow { register } => {
+                write!(f, "FIFO overflow reported in register 0x{:02X}", register)
+            }
+            Self::InvalidConfig { register, value } => write!(
+                f,
+                "Invalid configuration 0x{:02X} for register 0x{:02X}",
+                value, register
+            ),
+            Self::SelfTestFailed { register } => {
+                write!(f, "Self-test failed on axis of register 0x{:02X}", register)
+            }
+            Self::UnsupportedFeature { register } => write!(
+                f,
+                "Register 0x{:02X} is not supported by this chip variant",
+                register
+            ),
+            Self::VerificationMismatch {
+                register,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Register 0x{:02X} verification failed: wrote 0x{:02X}, read 0x{:02X}",
+                register, expected, actual
+            ),
         }
     }
 }
@@ -24,4 +198,48 @@ impl <E> From<E> for MPU6050Error<E> {
     fn from(e: E) -> Self {
         Self::I2CError(e)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal::spi;
+
+    #[test]
+    fn retry_classification() {
+        let nack = MPU6050Error::RegisterAccess {
+            register: 0x3B,
+            error: ErrorKind::NoAcknowledge(embedded_hal::i2c::NoAcknowledgeSource::Address),
+        };
+        assert!(nack.is_retryable());
+        let overrun = MPU6050Error::RegisterAccess {
+            register: 0x74,
+            error: spi::ErrorKind::Overrun,
+        };
+        assert!(overrun.is_retryable_spi());
+        let chip_select = MPU6050Error::RegisterAccess {
+            register: 0x74,
+            error: spi::ErrorKind::ChipSelectFault,
+        };
+        assert!(!chip_select.is_retryable_spi());
+        assert!(MPU6050Error::<spi::ErrorKind>::Timeout { register: 0x6B }.is_retryable_spi());
+        let invalid = MPU6050Error::<()>::InvalidConfig {
+            register: 0x23,
+            value: 0x07,
+        };
+        assert!(!invalid.is_retryable_with(|_| true));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn display_is_bus_neutral() {
+        let error = MPU6050Error::RegisterAccess {
+            register: 0x3B,
+            error: spi::ErrorKind::ModeFault,
+        };
+        assert_eq!(
+            std::format!("{error}"),
+            "Bus error accessing register 0x3B: ModeFault"
+        );
+    }
+}
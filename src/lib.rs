@@ -1,5 +1,20 @@
 #![no_std]
+#[cfg(feature = "std")]
+extern crate std;
+
 pub mod mpu6050;
+pub mod bus;
 pub mod errors;
 pub mod config;
-pub(crate) mod registers;
\ No newline at end of file
+pub mod compensation;
+pub mod dump;
+pub mod sample_log;
+pub mod timestamp;
+pub mod power;
+pub(crate) mod registers;
+#[cfg(feature = "sim")]
+pub mod sim;
+#[cfg(feature = "async")]
+pub mod data_ready;
+#[cfg(feature = "embassy")]
+pub mod embassy;
\ No newline at end of file
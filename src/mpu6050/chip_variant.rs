@@ -0,0 +1,101 @@
+use crate::registers::Registers;
+
+/// Member of the MPU-6050 family, identified by WHO_AM_I
+///
+/// The register map is shared, the differences handled by the driver are listed on the
+/// methods below. The MPU-6000 reports the same WHO_AM_I as the MPU-6050.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChipVariant {
+    /// MPU-6050 or MPU-6000
+    #[default]
+    Mpu6050,
+    Mpu6500,
+    /// MPU-9250, the MPU-6500 die with an AK8963 magnetometer on its auxiliary bus
+    Mpu9250,
+    Icm20602,
+}
+
+impl ChipVariant {
+    /// Identify the variant from the WHO_AM_I register value
+    pub const fn from_who_am_i(value: u8) -> Option<Self> {
+        match value {
+            0x68 => Some(Self::Mpu6050),
+            0x70 => Some(Self::Mpu6500),
+            0x71 => Some(Self::Mpu9250),
+            0x12 => Some(Self::Icm20602),
+            _ => None,
+        }
+    }
+
+    /// WHO_AM_I register value reported by the variant
+    pub const fn who_am_i(&self) -> u8 {
+        match self {
+            Self::Mpu6050 => 0x68,
+            Self::Mpu6500 => 0x70,
+            Self::Mpu9250 => 0x71,
+            Self::Icm20602 => 0x12,
+        }
+    }
+
+    /// FIFO size in bytes. The MPU-6500 and MPU-9250 default to 512 bytes.
+    pub const fn fifo_size(&self) -> u16 {
+        match self {
+            Self::Mpu6050 => 1024,
+            Self::Mpu6500 | Self::Mpu9250 => 512,
+            Self::Icm20602 => 1008,
+        }
+    }
+
+    /// Check for the separate accelerometer low pass filter in ACCEL_CONFIG2
+    pub const fn has_accel_dlpf(&self) -> bool {
+        !matches!(self, Self::Mpu6050)
+    }
+
+    /// Check for the accelerometer high pass filter bits in ACCEL_CONFIG
+    pub const fn has_accel_hpf(&self) -> bool {
+        matches!(self, Self::Mpu6050)
+    }
+
+    /// Check for the SELF_TEST_X..SELF_TEST_A factory trim layout used by
+    /// [`Mpu6050::self_test`](crate::mpu6050::Mpu6050::self_test)
+    pub const fn has_self_test(&self) -> bool {
+        matches!(self, Self::Mpu6050)
+    }
+
+    /// High byte registers of the X, Y and Z accelerometer offsets.
+    /// Each is followed by its low byte.
+    pub(crate) const fn accel_offset_registers(&self) -> [Registers; 3] {
+        match self {
+            Self::Mpu6050 => [
+                Registers::XaOffsUsrH,
+                Registers::YaOffsUsrH,
+                Registers::ZaOffsUsrH,
+            ],
+            Self::Mpu6500 | Self::Mpu9250 | Self::Icm20602 => [
+                Registers::XaOffsetH,
+                Registers::YaOffsetH,
+                Registers::ZaOffsetH,
+            ],
+        }
+    }
+
+    /// Temperature sensitivity in hundredths of LSB per °C
+    pub const fn temp_sensitivity_centi(&self) -> i32 {
+        match self {
+            Self::Mpu6050 => 34000,
+            Self::Mpu6500 | Self::Mpu9250 => 33387,
+            Self::Icm20602 => 32680,
+        }
+    }
+
+    /// Temperature at TEMP_OUT = 0 in hundredths of a degree Celsius
+    pub const fn temp_offset_centi(&self) -> i32 {
+        match self {
+            Self::Mpu6050 => 3653,
+            Self::Mpu6500 | Self::Mpu9250 => 2100,
+            Self::Icm20602 => 2500,
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use crate::bus::RegisterBus;
+use crate::errors::MPU6050Error;
+use crate::mpu6050::{FifoFrame, Mpu6050};
+
+/// Largest burst read used by [`Mpu6050::drain_fifo`], a whole number of frames is read per burst
+const DRAIN_CHUNK_LEN: usize = 64;
+
+/// Destination for frames drained from the FIFO, e.g. a ring buffer
+///
+/// Implemented for `heapless::Deque` with the `heapless` feature.
+pub trait FrameBuffer {
+    /// Number of frames that can still be pushed
+    fn free_frames(&self) -> usize;
+
+    /// Append a frame. Only called while [`Self::free_frames`] is non-zero.
+    fn push_frame(&mut self, frame: FifoFrame);
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> FrameBuffer for heapless::Deque<FifoFrame, N> {
+    fn free_frames(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    fn push_frame(&mut self, frame: FifoFrame) {
+        // Space was checked with free_frames
+        let _ = self.push_back(frame);
+    }
+}
+
+/// Outcome of [`Mpu6050::drain_fifo`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct FifoDrain {
+    available: usize,
+    drained: usize,
+}
+
+impl FifoDrain {
+    /// Complete frames in the FIFO when FIFO_COUNT was read
+    pub const fn available(&self) -> usize {
+        self.available
+    }
+
+    /// Frames moved into the frame buffer
+    pub const fn drained(&self) -> usize {
+        self.drained
+    }
+
+    /// Complete frames left in the FIFO because the frame buffer was full
+    pub const fn remaining(&self) -> usize {
+        self.available - self.drained
+    }
+}
+
+impl<BUS> Mpu6050<BUS>
+where
+    BUS: RegisterBus,
+{
+    /// Move all complete frames from the FIFO into `frames`, as far as it has space.
+    ///
+    /// Reads FIFO_COUNT once and then bursts of at most 64 bytes, each a whole number of
+    /// frames and further limited by [`Self::set_max_transfer_len`]. A partially written frame
+    /// and frames that don't fit into `frames` stay in the FIFO for the next call. Returns the
+    /// number of frames available and added, pass both to
+    /// [`FifoTimestamper::add_batch`](crate::timestamp::FifoTimestamper::add_batch) together
+    /// with the host time taken right before the call.
+    ///
+    /// Frames are decoded as with [`Self::read_fifo_frame`], with the same
+    /// [`MPU6050Error::InvalidConfig`] for a FIFO_EN without sensors or with external slaves.
+    /// After a FIFO overflow the frame boundaries are lost, check
+    /// [`Self::check_fifo_overflow`] and reset the FIFO first.
+    pub fn drain_fifo(
+        &mut self,
+        frames: &mut impl FrameBuffer,
+    ) -> Result<FifoDrain, MPU6050Error<BUS::Error>> {
+        let frame_size = self.fifo_frame_size()?;
+        let available = self.get_fifo_count()? as usize / frame_size;
+        let total = available.min(frames.free_frames());
+        let frames_per_chunk = DRAIN_CHUNK_LEN / frame_size;
+
+        let mut buffer = [0u8; DRAIN_CHUNK_LEN];
+        let mut remaining = total;
+        while remaining > 0 {
+            let count = remaining.min(frames_per_chunk);
+            let chunk = &mut buffer[..count * frame_size];
+            self.read_fifo(chunk)?;
+            for bytes in chunk.chunks_exact(frame_size) {
+                frames.push_frame(self.decode_fifo_frame(bytes));
+            }
+            remaining -= count;
+        }
+        Ok(FifoDrain {
+            available,
+            drained: total,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sim"))]
+mod tests {
+    use super::*;
+    use crate::config::{DeviceConfig, DlpfConfig, FifoConfig};
+    use crate::mpu6050::Vector3;
+    use crate::sim::SimulatedMpu6050;
+
+    const FIFO_EN: u8 = 0x23;
+
+    /// Ring buffer of eight frames
+    struct Ring {
+        frames: [Option<FifoFrame>; 8],
+        head: usize,
+        len: usize,
+    }
+
+    impl Ring {
+        fn with_frames(count: usize) -> Self {
+            let mut ring = Self {
+                frames: [None; 8],
+                head: 5,
+                len: 0,
+            };
+            for _ in 0..count {
+                ring.push_frame(FifoFrame {
+                    accel: None,
+                    temp: None,
+                    gyro: None,
+                });
+            }
+            ring
+        }
+
+        fn pop(&mut self) -> Option<FifoFrame> {
+            let frame = self.frames[self.head].take()?;
+            self.head = (self.head + 1) % self.frames.len();
+            self.len -= 1;
+            Some(frame)
+        }
+    }
+
+    impl FrameBuffer for Ring {
+        fn free_frames(&self) -> usize {
+            self.frames.len() - self.len
+        }
+
+        fn push_frame(&mut self, frame: FifoFrame) {
+            let tail = (self.head + self.len) % self.frames.len();
+            self.frames[tail] = Some(frame);
+            self.len += 1;
+        }
+    }
+
+    fn full_frames() -> FifoConfig {
+        FifoConfig::new()
+            .enable_accel(true)
+            .enable_temp(true)
+            .enable_gyro_all(true)
+    }
+
+    /// Driver with ten frames of accelerometer, temperature and gyroscope data in the FIFO
+    fn filled_fifo() -> Mpu6050<SimulatedMpu6050> {
+        let mut sim = SimulatedMpu6050::default();
+        sim.set_accel_raw(1, 2, 3);
+        sim.set_gyro_raw(-4, 5, 6);
+        let config = DeviceConfig::new()
+            .with_dlpf(DlpfConfig::Dlpf1)
+            .with_sample_rate_divider(9)
+            .with_fifo(full_frames(), true);
+        let mut mpu = Mpu6050::new(sim, 0x68);
+        mpu.apply_config(&config).unwrap();
+        let mut sim = mpu.release();
+        sim.advance_us(100_000);
+        let mut mpu = Mpu6050::new(sim, 0x68);
+        mpu.configure_fifo(full_frames()).unwrap();
+        mpu
+    }
+
+    #[test]
+    fn partial_drain_into_nearly_full_ring() {
+        let mut mpu = filled_fifo();
+        assert_eq!(mpu.get_fifo_count().unwrap(), 140);
+
+        let mut ring = Ring::with_frames(6);
+        let drain = mpu.drain_fifo(&mut ring).unwrap();
+        assert_eq!(
+            (drain.available(), drain.drained(), drain.remaining()),
+            (10, 2, 8)
+        );
+        assert_eq!(mpu.get_fifo_count().unwrap(), 8 * 14);
+        assert_eq!(ring.free_frames(), 0);
+
+        // The ring wraps around while the rest is drained
+        for _ in 0..6 {
+            assert_eq!(ring.pop().unwrap().accel, None);
+        }
+        let drain = mpu.drain_fifo(&mut ring).unwrap();
+        assert_eq!((drain.available(), drain.drained()), (8, 6));
+        for _ in 0..8 {
+            let frame = ring.pop().unwrap();
+            assert_eq!(frame.accel, Some(Vector3::new(1, 2, 3)));
+            assert_eq!(frame.gyro, Some(Vector3::new(-4, 5, 6)));
+        }
+        let drain = mpu.drain_fifo(&mut ring).unwrap();
+        assert_eq!((drain.available(), drain.drained()), (2, 2));
+        assert_eq!(mpu.get_fifo_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn slave_data_is_rejected() {
+        let mut mpu = filled_fifo();
+        let fifo = full_frames().enable_slave1(true);
+        mpu.configure_fifo(fifo).unwrap();
+        let mut ring = Ring::with_frames(0);
+        match mpu.drain_fifo(&mut ring) {
+            Err(MPU6050Error::InvalidConfig { register, value }) => {
+                assert_eq!(register, FIFO_EN);
+                assert_eq!(value, fifo.register_value());
+            }
+            other => panic!("expected InvalidConfig, got {other:?}"),
+        }
+        assert!(mpu.read_fifo_frame().is_err());
+        assert_eq!(mpu.get_fifo_count().unwrap(), 140);
+    }
+}
@@ -0,0 +1,58 @@
+use crate::config::FifoConfig;
+use crate::mpu6050::{Temperature, Vector3};
+
+/// One sample as written to the FIFO
+///
+/// The device writes the enabled sensors in ascending register order: accelerometer,
+/// temperature, then the enabled gyroscope axes. Fields of sensors not in FIFO_EN are `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FifoFrame {
+    pub accel: Option<Vector3>,
+    pub temp: Option<Temperature>,
+    /// Present if any gyroscope axis is enabled, axes not in the FIFO read 0
+    pub gyro: Option<Vector3>,
+}
+
+impl FifoFrame {
+    /// Largest frame the internal sensors produce, see [`FifoConfig::frame_size`]
+    pub const MAX_LEN: usize = 14;
+
+    /// Decode one frame from the start of `bytes`, which must hold at least
+    /// `fifo_config.frame_size()` bytes
+    pub fn from_be_bytes(bytes: &[u8], fifo_config: FifoConfig) -> Self {
+        let mut offset = 0;
+        let mut next = || {
+            let value = i16::from_be_bytes([bytes[offset], bytes[offset + 1]]);
+            offset += 2;
+            value
+        };
+
+        let accel = if fifo_config.is_accel_enabled() {
+            Some(Vector3::new(next(), next(), next()))
+        } else {
+            None
+        };
+        let temp = if fifo_config.is_temp_enabled() {
+            Some(Temperature::from_raw(next()))
+        } else {
+            None
+        };
+        let mut axis = |enabled: bool| if enabled { next() } else { 0 };
+        let gyro = Vector3::new(
+            axis(fifo_config.is_gyro_x_enabled()),
+            axis(fifo_config.is_gyro_y_enabled()),
+            axis(fifo_config.is_gyro_z_enabled()),
+        );
+        let any_gyro = fifo_config.is_gyro_x_enabled()
+            || fifo_config.is_gyro_y_enabled()
+            || fifo_config.is_gyro_z_enabled();
+
+        Self {
+            accel,
+            temp,
+            gyro: if any_gyro { Some(gyro) } else { None },
+        }
+    }
+}
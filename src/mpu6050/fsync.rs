@@ -0,0 +1,35 @@
+use crate::config::ExtSync;
+use crate::mpu6050::Sample;
+
+/// [`Sample`] with the FSYNC flag split off
+///
+/// When EXT_SYNC_SET is active the device replaces the LSB of one data register with the FSYNC
+/// level latched since the previous sample. Aligning samples with an external event, such as a
+/// camera shutter pulse, only needs to look for `fsync == Some(true)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FsyncSample {
+    /// Sensor data with the LSB carrying the flag cleared
+    pub sample: Sample,
+    /// FSYNC flag, `None` if FSYNC is disabled
+    pub fsync: Option<bool>,
+}
+
+impl FsyncSample {
+    /// Create from the register bytes ACCEL_XOUT_H to GYRO_ZOUT_L
+    pub const fn from_be_bytes(mut bytes: [u8; Sample::LEN], ext_sync: ExtSync) -> Self {
+        let fsync = match ext_sync.sample_byte_index() {
+            Some(index) => {
+                let flag = bytes[index] & 0x01 != 0;
+                bytes[index] &= !0x01;
+                Some(flag)
+            }
+            None => None,
+        };
+        Self {
+            sample: Sample::from_be_bytes(bytes),
+            fsync,
+        }
+    }
+}
@@ -1,3 +1,23 @@
 
+pub mod chip_variant;
+pub mod fifo_drain;
+pub mod fifo_frame;
+pub mod fsync;
 pub mod mpu6050;
-pub use mpu6050::Mpu6050;
\ No newline at end of file
+mod rounding;
+pub mod sample;
+pub mod self_test;
+pub mod temperature;
+pub mod typestate;
+pub mod vector3;
+pub use chip_variant::ChipVariant;
+pub use fifo_drain::{FifoDrain, FrameBuffer};
+pub use fifo_frame::FifoFrame;
+pub use fsync::FsyncSample;
+pub use mpu6050::Mpu6050;
+pub(crate) use rounding::round_to_i16;
+pub use sample::{Sample, SampleSource};
+pub use self_test::SelfTestResult;
+pub use temperature::Temperature;
+pub use typestate::{Awake, CycleMode, FifoStreaming, Sleeping, TransitionError, TypedMpu6050};
+pub use vector3::{Arithmetic, Vector3};
@@ -1,58 +1,336 @@
 use crate::config::{
-    AccelConfig, DlpfConfig, ExtSync, FifoConfig, GyroConfig, IntPinConfig,
-    InterruptEnable,
-    PwrMgmt1,
+    AccelConfig, AccelDlpf, AccelHpf, AccelSettings, ClockSource, DlpfConfig, ExtSync, FifoConfig, GyroConfig, IntPinConfig,
+    InterruptEnable, Orientation,
+    PwrMgmt1, PwrMgmt2,
 };
+use crate::bus::{RegisterBus, SpiInterface};
+use crate::config::DeviceConfig;
 use crate::errors::MPU6050Error;
+pub use crate::mpu6050::vector3::Vector3;
+use crate::mpu6050::{ChipVariant, FifoFrame, FsyncSample, Sample, SampleSource, Temperature};
 use crate::registers::Registers;
+use embedded_hal::delay::DelayNs;
 use embedded_hal::i2c::SevenBitAddress;
 
-/// Helper struct for returning 3D vector data (accel or gyro)
-#[derive(Debug, Clone, Copy)]
-pub struct Vector3 {
-    pub x: i16,
-    pub y: i16,
-    pub z: i16,
-}
+/// Gyroscope start-up time from sleep, datasheet typical value
+const GYRO_STARTUP_MS: u32 = 30;
+
+/// Shortest read transfer limit, a full sample burst from ACCEL_XOUT_H to GYRO_ZOUT_L
+const MIN_TRANSFER_LEN: usize = Sample::LEN;
 
-pub struct Mpu6050<I2C> {
-    i2c: I2C,
+/// DEVICE_RESET polling for [`Mpu6050::reset_device`], 100 ms in total
+const RESET_POLL_COUNT: u32 = 10;
+const RESET_POLL_INTERVAL_MS: u32 = 10;
+
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct Mpu6050<BUS> {
+    bus: BUS,
     address: SevenBitAddress,
+    /// Last EXT_SYNC_SET written, tells which data LSB carries the FSYNC flag
+    ext_sync: ExtSync,
+    /// Last FIFO_EN written, gives the layout of FIFO frames
+    fifo_config: FifoConfig,
+    /// Mounting orientation applied to accelerometer and gyroscope reads
+    orientation: Orientation,
+    /// Family member, selects variant-specific registers
+    variant: ChipVariant,
+    /// Longest single read transfer the bus controller supports
+    max_transfer_len: usize,
 }
 
-impl<I2C> Mpu6050<I2C>
+impl<BUS> Mpu6050<BUS>
 where
-    I2C: embedded_hal::i2c::I2c,
+    BUS: RegisterBus,
 {
-    pub const fn new(i2c: I2C, address: SevenBitAddress) -> Self {
-        Self { i2c, address }
+    /// Create driver for a device on `bus`. `address` is the I2C address (0x68 or 0x69),
+    /// it is ignored on SPI.
+    pub const fn new(bus: BUS, address: SevenBitAddress) -> Self {
+        Self {
+            bus,
+            address,
+            ext_sync: ExtSync::Disabled,
+            fifo_config: FifoConfig::new(),
+            orientation: Orientation::IDENTITY,
+            variant: ChipVariant::Mpu6050,
+            max_transfer_len: usize::MAX,
+        }
+    }
+
+    /// Limit the length of a single read transfer, see [`Self::set_max_transfer_len`]
+    pub const fn with_max_transfer_len(mut self, len: usize) -> Self {
+        self.max_transfer_len = clamp_transfer_len(len);
+        self
+    }
+
+    /// Limit the length of a single read transfer, for bus controllers that cap transfers
+    /// (e.g. at 32 or 255 bytes). Unlimited by default.
+    ///
+    /// Limits below 14 bytes are raised to 14, so [`Self::read_sample`] always reads the data
+    /// registers in one transfer and a sample is never torn between two sampling instants.
+    /// Longer burst and FIFO reads are split into several transfers, FIFO reads at frame
+    /// boundaries.
+    pub fn set_max_transfer_len(&mut self, len: usize) {
+        self.max_transfer_len = clamp_transfer_len(len);
+    }
+
+    /// Get the longest single read transfer
+    pub const fn max_transfer_len(&self) -> usize {
+        self.max_transfer_len
+    }
+
+    /// Set the chip variant without reading WHO_AM_I, see [`Self::detect_variant`]
+    pub const fn with_variant(mut self, variant: ChipVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Get the chip variant, [`ChipVariant::Mpu6050`] until set or detected
+    pub const fn variant(&self) -> ChipVariant {
+        self.variant
+    }
+
+    /// FIFO size in bytes of the chip variant
+    pub const fn fifo_size(&self) -> u16 {
+        self.variant.fifo_size()
+    }
+
+    /// Set the mounting orientation, see [`Self::set_orientation`]
+    pub const fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Set the mounting orientation of the sensor.
+    ///
+    /// [`Self::read_accel`], [`Self::read_gyro`], the sample reads and [`Self::read_fifo_frame`]
+    /// return body frame values from then on. Calibration offsets measured in the sensor frame
+    /// have to be converted with [`Calibration::to_body_frame`](crate::compensation::Calibration::to_body_frame).
+    /// The offset registers ([`Self::write_accel_offsets`], [`Self::write_gyro_offsets`]) act
+    /// before the orientation and stay in the sensor frame.
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+
+    /// Get the mounting orientation
+    pub const fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Destroy the driver and return the bus
+    pub fn release(self) -> BUS {
+        self.bus
+    }
+
+    /// Read a single register.
+    pub(crate) fn read_register(&mut self, register: Registers) -> Result<u8, MPU6050Error<BUS::Error>> {
+        let mut buffer = [0u8];
+        self.read_burst(register, &mut buffer)?;
+        Ok(buffer[0])
+    }
+
+    /// Read `buffer.len()` bytes starting at `register`.
+    /// Bus errors are reported as [`MPU6050Error::RegisterAccess`] for `register`.
+    pub(crate) fn read_burst(
+        &mut self,
+        register: Registers,
+        buffer: &mut [u8],
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.read_chunked(register.get_register_address(), buffer)
+    }
+
+    /// Burst read split into transfers of at most `max_transfer_len` bytes.
+    ///
+    /// Each transfer continues at the register after the previous one, except for FIFO_R_W,
+    /// which keeps returning the next FIFO byte.
+    fn read_chunked(&mut self, start: u8, buffer: &mut [u8]) -> Result<(), MPU6050Error<BUS::Error>> {
+        let fifo = start == Registers::FifoRW.get_register_address();
+        let frame_size = self.fifo_config.frame_size();
+        // The limit is at least one sample long, so it holds at least one frame
+        let chunk_len = if fifo && frame_size > 0 {
+            self.max_transfer_len - self.max_transfer_len % frame_size
+        } else {
+            self.max_transfer_len
+        };
+
+        let mut register = start;
+        for chunk in buffer.chunks_mut(chunk_len) {
+            self.bus
+                .read_registers(self.address, register, chunk)
+                .map_err(|error| MPU6050Error::RegisterAccess { register, error })?;
+            if !fifo {
+                register = register.wrapping_add(chunk.len() as u8);
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a single register.
+    pub(crate) fn write_register(
+        &mut self,
+        register: Registers,
+        value: u8,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.write_address(register.get_register_address(), value)
+    }
+
+    /// Write a single register by address, for registers without their own [`Registers`] entry
+    /// such as the low bytes of the offsets.
+    fn write_address(&mut self, address: u8, value: u8) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.bus
+            .write_register(self.address, address, value)
+            .map_err(|error| MPU6050Error::RegisterAccess {
+                register: address,
+                error,
+            })
+    }
+
+    /// Write a register and read it back.
+    /// Only the bits set in `mask` are compared, so self-clearing bits can be excluded.
+    pub(crate) fn write_register_verified(
+        &mut self,
+        register: Registers,
+        value: u8,
+        mask: u8,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.write_register(register, value)?;
+        let actual = self.read_register(register)?;
+        if actual & mask != value & mask {
+            return Err(MPU6050Error::VerificationMismatch {
+                register: register.get_register_address(),
+                expected: value & mask,
+                actual: actual & mask,
+            });
+        }
+        Ok(())
     }
 
     pub fn configure_gyro(
         &mut self,
         gyro_conf: GyroConfig,
-    ) -> Result<(), MPU6050Error<I2C::Error>> {
-        self.i2c.write(
-            self.address,
-            &[
-                Registers::GyroConfig.get_register_address(),
-                gyro_conf.register_value(),
-            ],
-        )?;
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.write_register(Registers::GyroConfig, gyro_conf.register_value())?;
         Ok(())
     }
 
     pub fn configure_accel(
         &mut self,
         accel_conf: AccelConfig,
-    ) -> Result<(), MPU6050Error<I2C::Error>> {
-        self.i2c.write(
-            self.address,
-            &[
-                Registers::AccelConfig.get_register_address(),
-                accel_conf.register_value(),
-            ],
-        )?;
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.write_register(Registers::AccelConfig, accel_conf.register_value())?;
+        Ok(())
+    }
+
+    /// Read the gyroscope full scale range from GYRO_CONFIG
+    pub fn read_gyro_config(&mut self) -> Result<GyroConfig, MPU6050Error<BUS::Error>> {
+        Ok(GyroConfig::from_register(self.read_register(Registers::GyroConfig)?))
+    }
+
+    /// Read the accelerometer full scale range from ACCEL_CONFIG
+    pub fn read_accel_config(&mut self) -> Result<AccelConfig, MPU6050Error<BUS::Error>> {
+        Ok(AccelConfig::from_register(self.read_register(Registers::AccelConfig)?))
+    }
+
+    /// Configure range, self-test and high pass filter of the accelerometer at once.
+    /// See [`AccelHpf`] for the effect of the filter on motion detection.
+    ///
+    /// Returns [`MPU6050Error::UnsupportedFeature`] if the filter is not in reset and the chip
+    /// variant has no accelerometer high pass filter.
+    pub fn configure_accel_settings(
+        &mut self,
+        accel_settings: AccelSettings,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.check_accel_hpf(accel_settings.hpf())?;
+        self.write_register(Registers::AccelConfig, accel_settings.register_value())
+    }
+
+    /// Configure the accelerometer low pass filter (ACCEL_CONFIG2).
+    ///
+    /// Returns [`MPU6050Error::UnsupportedFeature`] on the MPU-6050, whose accelerometer is
+    /// filtered by the DLPF in CONFIG.
+    pub fn configure_accel_dlpf(
+        &mut self,
+        accel_dlpf: AccelDlpf,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.require(self.variant.has_accel_dlpf(), Registers::AccelConfig2)?;
+        self.write_register(Registers::AccelConfig2, accel_dlpf.register_value())
+    }
+
+    fn check_accel_hpf(&self, hpf: Option<AccelHpf>) -> Result<(), MPU6050Error<BUS::Error>> {
+        let supported = self.variant.has_accel_hpf() || hpf == Some(AccelHpf::Reset);
+        self.require(supported, Registers::AccelConfig)
+    }
+
+    /// Report [`MPU6050Error::UnsupportedFeature`] for `register` unless `supported`
+    pub(crate) fn require(
+        &self,
+        supported: bool,
+        register: Registers,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        if supported {
+            Ok(())
+        } else {
+            Err(MPU6050Error::UnsupportedFeature {
+                register: register.get_register_address(),
+            })
+        }
+    }
+
+    /// Read the accelerometer offsets, in units of the ±16 g range.
+    ///
+    /// Offsets are per sensor axis, the orientation is not applied.
+    ///
+    /// The registers are at 0x06 on the MPU-6050 and at 0x77 on the MPU-6500 family.
+    pub fn read_accel_offsets(&mut self) -> Result<Vector3, MPU6050Error<BUS::Error>> {
+        let registers = self.variant.accel_offset_registers();
+        let mut axes = [0i16; 3];
+        for (axis, register) in axes.iter_mut().zip(registers) {
+            let mut buffer = [0u8; 2];
+            self.read_burst(register, &mut buffer)?;
+            *axis = i16::from_be_bytes(buffer);
+        }
+        Ok(axes.into())
+    }
+
+    /// Write the accelerometer offsets, in units of the ±16 g range.
+    ///
+    /// Bit 0 of each offset is reserved for temperature compensation, it is preserved and the
+    /// lowest bit of `offsets` is ignored.
+    pub fn write_accel_offsets(&mut self, offsets: Vector3) -> Result<(), MPU6050Error<BUS::Error>> {
+        let current = self.read_accel_offsets()?;
+        let registers = self.variant.accel_offset_registers();
+        let axes = [
+            (offsets.x, current.x),
+            (offsets.y, current.y),
+            (offsets.z, current.z),
+        ];
+        for ((offset, current), register) in axes.into_iter().zip(registers) {
+            let [high, low] = ((offset & !1) | (current & 1)).to_be_bytes();
+            self.write_register(register, high)?;
+            self.write_address(register.get_register_address() + 1, low)?;
+        }
+        Ok(())
+    }
+
+    /// Read the gyroscope offsets, in units of the ±1000 °/s range.
+    ///
+    /// Offsets are per sensor axis, the orientation is not applied.
+    pub fn read_gyro_offsets(&mut self) -> Result<Vector3, MPU6050Error<BUS::Error>> {
+        self.read_vector(Registers::XgOffsUsrH)
+    }
+
+    /// Write the gyroscope offsets, in units of the ±1000 °/s range.
+    pub fn write_gyro_offsets(&mut self, offsets: Vector3) -> Result<(), MPU6050Error<BUS::Error>> {
+        let registers = [
+            Registers::XgOffsUsrH,
+            Registers::YgOffsUsrH,
+            Registers::ZgOffsUsrH,
+        ];
+        for (register, axis) in registers.into_iter().zip([offsets.x, offsets.y, offsets.z]) {
+            let [high, low] = axis.to_be_bytes();
+            self.write_register(register, high)?;
+            self.write_address(register.get_register_address() + 1, low)?;
+        }
         Ok(())
     }
 
@@ -60,80 +338,183 @@ where
         &mut self,
         dlpf_config: DlpfConfig,
         ext_sync: ExtSync,
-    ) -> Result<(), MPU6050Error<I2C::Error>> {
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.ext_sync = ext_sync;
         let config_value = (ext_sync.get_register_value() << 3) | dlpf_config.register_value();
-        self.i2c.write(
-            self.address,
-            &[Registers::Config.get_register_address(), config_value],
-        )?;
+        self.write_register(Registers::Config, config_value)?;
         Ok(())
     }
 
     pub fn configure_interrupt_enable(
         &mut self,
         interrupt_enable: InterruptEnable,
-    ) -> Result<(), MPU6050Error<I2C::Error>> {
-        self.i2c.write(
-            self.address,
-            &[
-                Registers::InterruptEnable.get_register_address(),
-                interrupt_enable.register_value(),
-            ],
-        )?;
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.write_register(Registers::InterruptEnable, interrupt_enable.register_value())?;
         Ok(())
     }
 
     pub fn configure_interrupt_pin(
         &mut self,
         interrupt_pin_conf: IntPinConfig,
-    ) -> Result<(), MPU6050Error<I2C::Error>> {
-        self.i2c.write(
-            self.address,
-            &[
-                Registers::InterruptPinCfg.get_register_address(),
-                interrupt_pin_conf.register_value(),
-            ],
-        )?;
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.write_register(Registers::InterruptPinCfg, interrupt_pin_conf.register_value())?;
         Ok(())
     }
 
     pub fn configure_power_management(
         &mut self,
         pwr_mgmt: PwrMgmt1,
-    ) -> Result<(), MPU6050Error<I2C::Error>> {
-        self.i2c.write(
-            self.address,
-            &[
-                Registers::PowerMgmt1.get_register_address(),
-                pwr_mgmt.register_value(),
-            ],
-        )?;
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.write_register(Registers::PowerMgmt1, pwr_mgmt.register_value())?;
         Ok(())
     }
 
+    /// Read PWR_MGMT_1
+    pub fn read_power_management(&mut self) -> Result<PwrMgmt1, MPU6050Error<BUS::Error>> {
+        Ok(PwrMgmt1::from_register(self.read_register(Registers::PowerMgmt1)?))
+    }
+
+    /// Put the device to sleep. Configuration is kept, the data registers stop updating.
+    pub fn sleep(&mut self) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.update_pwr_mgmt_1(|pwr| pwr.set_sleep(true))
+    }
+
+    /// Wake the device up using the X axis gyroscope PLL as clock source, see
+    /// [`Self::wake_discarding`].
+    pub fn wake(&mut self, delay: &mut impl DelayNs) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.wake_discarding(delay, 0)
+    }
+
+    /// Wake the device up using the X axis gyroscope PLL as clock source and wait until its
+    /// output is valid.
+    ///
+    /// Waits the 30 ms gyroscope start-up time, then reads and drops `samples` samples from the
+    /// data registers, one sample period at the configured sample rate apart. Finally the FIFO
+    /// is reset with [`Self::reset_fifo`] so the samples taken while settling are discarded
+    /// there as well, FIFO_EN is left as it was.
+    pub fn wake_discarding(
+        &mut self,
+        delay: &mut impl DelayNs,
+        samples: u32,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.update_pwr_mgmt_1(|pwr| {
+            pwr.set_sleep(false)
+                .set_cycle(false)
+                .set_clock_source(ClockSource::PllXGyro)
+        })?;
+        delay.delay_ms(GYRO_STARTUP_MS);
+        if samples > 0 {
+            let period_us = 1_000_000 / self.read_sample_rate_hz()?;
+            for _ in 0..samples {
+                delay.delay_us(period_us);
+                self.read_sample()?;
+            }
+        }
+        self.reset_fifo()
+    }
+
+    /// Read the sample rate in Hz resulting from the DLPF setting in CONFIG and SMPLRT_DIV
+    pub fn read_sample_rate_hz(&mut self) -> Result<u32, MPU6050Error<BUS::Error>> {
+        // SMPLRT_DIV is directly followed by CONFIG
+        let mut buffer = [0u8; 2];
+        self.read_burst(Registers::SmprtDiv, &mut buffer)?;
+        // The reserved DLPF_CFG value 7 runs the gyroscope at 8 kHz like DLPF_CFG 0
+        let dlpf = DlpfConfig::from_register(buffer[1]).unwrap_or(DlpfConfig::Dlpf0);
+        Ok(dlpf.gyro_output_rate_hz() / (1 + buffer[0] as u32))
+    }
+
+    pub fn configure_power_management_2(
+        &mut self,
+        pwr_mgmt_2: PwrMgmt2,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.write_register(Registers::PowerMgmt2, pwr_mgmt_2.register_value())
+    }
+
     pub fn configure_fifo(
         &mut self,
         fifo_config: FifoConfig,
-    ) -> Result<(), MPU6050Error<I2C::Error>> {
-        self.i2c.write(
-            self.address,
-            &[
-                Registers::FifoEn.get_register_address(),
-                fifo_config.register_value(),
-            ],
-        )?;
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.write_register(Registers::FifoEn, fifo_config.register_value())?;
+        self.fifo_config = fifo_config;
+        Ok(())
+    }
+
+    /// Read the WHO_AM_I register.
+    /// The MPU-6050 reports 0x68 regardless of the AD0 pin, see [`ChipVariant::who_am_i`]
+    /// for the other family members.
+    pub fn who_am_i(&mut self) -> Result<u8, MPU6050Error<BUS::Error>> {
+        self.read_register(Registers::WhoAmI)
+    }
+
+    /// Check that WHO_AM_I matches the chip variant, see [`MPU6050Error::WrongDeviceId`]
+    pub fn verify_device_id(&mut self) -> Result<(), MPU6050Error<BUS::Error>> {
+        let actual = self.who_am_i()?;
+        let expected = self.variant.who_am_i();
+        if actual != expected {
+            return Err(MPU6050Error::WrongDeviceId {
+                register: Registers::WhoAmI.get_register_address(),
+                expected,
+                actual,
+            });
+        }
         Ok(())
     }
 
+    /// Identify the family member from WHO_AM_I and use its variant-specific registers.
+    ///
+    /// Returns [`MPU6050Error::WrongDeviceId`] if WHO_AM_I is not a known value, the variant
+    /// is left unchanged then.
+    pub fn detect_variant(&mut self) -> Result<ChipVariant, MPU6050Error<BUS::Error>> {
+        let actual = self.who_am_i()?;
+        let variant = ChipVariant::from_who_am_i(actual).ok_or(MPU6050Error::WrongDeviceId {
+            register: Registers::WhoAmI.get_register_address(),
+            expected: self.variant.who_am_i(),
+            actual,
+        })?;
+        self.variant = variant;
+        Ok(variant)
+    }
+
+    /// Reset all registers to their power-on values with DEVICE_RESET and wait until the
+    /// bit clears. The device is asleep afterwards.
+    ///
+    /// Returns [`MPU6050Error::Timeout`] if the reset does not complete within 100 ms.
+    pub fn reset_device(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        let reset = PwrMgmt1::new().device_reset(true);
+        self.write_register(Registers::PowerMgmt1, reset.register_value())?;
+        for _ in 0..RESET_POLL_COUNT {
+            delay.delay_ms(RESET_POLL_INTERVAL_MS);
+            let pwr = PwrMgmt1::from_register(self.read_register(Registers::PowerMgmt1)?);
+            if !pwr.is_device_reset() {
+                self.ext_sync = ExtSync::Disabled;
+                self.fifo_config = FifoConfig::new();
+                return Ok(());
+            }
+        }
+        Err(MPU6050Error::Timeout {
+            register: Registers::PowerMgmt1.get_register_address(),
+        })
+    }
+
+    /// Burst read consecutive registers starting at `start` into `buffer`.
+    pub fn read_registers(&mut self, start: u8, buffer: &mut [u8]) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.read_chunked(start, buffer)
+    }
+
+    /// Set the sample rate divider (SMPLRT_DIV register).
+    /// Sample rate = gyroscope output rate / (1 + divider).
+    pub fn configure_sample_rate_divider(&mut self, divider: u8) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.write_register(Registers::SmprtDiv, divider)
+    }
+
     /// Enable or disable the FIFO buffer.
     /// This sets bit 6 in the USER_CTRL register.
-    pub fn set_fifo_enabled(&mut self, enable: bool) -> Result<(), MPU6050Error<I2C::Error>> {
+    pub fn set_fifo_enabled(&mut self, enable: bool) -> Result<(), MPU6050Error<BUS::Error>> {
         let mut buffer = [0u8];
-        self.i2c.write_read(
-            self.address,
-            &[Registers::UserCtrl.get_register_address()],
-            &mut buffer,
-        )?;
+        self.read_burst(Registers::UserCtrl, &mut buffer)?;
         let mut value = buffer[0];
 
         if enable {
@@ -142,64 +523,98 @@ where
             value &= !(1 << 6);
         }
 
-        self.i2c.write(
-            self.address,
-            &[Registers::UserCtrl.get_register_address(), value],
-        )?;
+        self.write_register(Registers::UserCtrl, value)?;
         Ok(())
     }
 
     /// Reset the FIFO buffer.
-    /// This sets bit 2 in the USER_CTRL register.
-    /// The bit automatically clears to 0.
-    pub fn reset_fifo(&mut self) -> Result<(), MPU6050Error<I2C::Error>> {
+    ///
+    /// FIFO_RESET (bit 2 of USER_CTRL) only takes effect while FIFO_EN (bit 6) is cleared, so
+    /// the FIFO is disabled for the reset and FIFO_EN is restored afterwards. The reset bit
+    /// clears automatically.
+    pub fn reset_fifo(&mut self) -> Result<(), MPU6050Error<BUS::Error>> {
         let mut buffer = [0u8];
-        self.i2c.write_read(
-            self.address,
-            &[Registers::UserCtrl.get_register_address()],
-            &mut buffer,
-        )?;
-        let mut value = buffer[0];
+        self.read_burst(Registers::UserCtrl, &mut buffer)?;
+        let value = buffer[0] & !(1 << 2);
 
-        value |= 1 << 2;
-
-        self.i2c.write(
-            self.address,
-            &[Registers::UserCtrl.get_register_address(), value],
-        )?;
+        self.write_register(Registers::UserCtrl, (value & !(1 << 6)) | (1 << 2))?;
+        if value & (1 << 6) != 0 {
+            self.write_register(Registers::UserCtrl, value)?;
+        }
         Ok(())
     }
 
     /// Get current number of bytes in FIFO buffer.
-    pub fn get_fifo_count(&mut self) -> Result<u16, MPU6050Error<I2C::Error>> {
+    pub fn get_fifo_count(&mut self) -> Result<u16, MPU6050Error<BUS::Error>> {
         let mut buffer = [0u8; 2];
-        self.i2c.write_read(
-            self.address,
-            &[Registers::FifoCountH.get_register_address()],
-            &mut buffer,
-        )?;
+        self.read_burst(Registers::FifoCountH, &mut buffer)?;
         Ok(u16::from_be_bytes(buffer))
     }
 
+    /// Check the FIFO_OFLOW_INT bit of INT_STATUS and report an overflow as
+    /// [`MPU6050Error::FifoOverflow`]. Reset the FIFO afterwards with [`Self::reset_fifo`], the
+    /// frame boundaries are lost.
+    ///
+    /// Reading INT_STATUS clears all interrupt flags, including DATA_RDY.
+    pub fn check_fifo_overflow(&mut self) -> Result<(), MPU6050Error<BUS::Error>> {
+        let status = self.read_register(Registers::InterruptStatus)?;
+        if status & (1 << 4) != 0 {
+            return Err(MPU6050Error::FifoOverflow {
+                register: Registers::InterruptStatus.get_register_address(),
+            });
+        }
+        Ok(())
+    }
+
     /// Read data from FIFO buffer.
     /// The buffer length determines how many bytes are read.
-    pub fn read_fifo(&mut self, buffer: &mut [u8]) -> Result<(), MPU6050Error<I2C::Error>> {
-        self.i2c.write_read(
-            self.address,
-            &[Registers::FifoRW.get_register_address()],
-            buffer,
-        )?;
-        Ok(())
+    pub fn read_fifo(&mut self, buffer: &mut [u8]) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.read_burst(Registers::FifoRW, buffer)
     }
 
-    /// Reads raw accelerometer data for X, Y, and Z axes.
-    pub fn read_accel(&mut self) -> Result<Vector3, MPU6050Error<I2C::Error>> {
+    /// Read the next frame from the FIFO, laid out according to the last FIFO_EN written.
+    ///
+    /// Returns [`MPU6050Error::InvalidConfig`] if no sensor or any external slave (SLV0 to SLV2)
+    /// is written to the FIFO. The
+    /// orientation is applied to the accelerometer, and to the gyroscope if all three of its
+    /// axes are in the FIFO.
+    pub fn read_fifo_frame(&mut self) -> Result<FifoFrame, MPU6050Error<BUS::Error>> {
+        let frame_size = self.fifo_frame_size()?;
+        let mut buffer = [0u8; FifoFrame::MAX_LEN];
+        self.read_fifo(&mut buffer[..frame_size])?;
+        Ok(self.decode_fifo_frame(&buffer[..frame_size]))
+    }
+
+    /// Size of a FIFO frame, [`MPU6050Error::InvalidConfig`] if no sensor is written to the FIFO
+    /// or external slave data is, whose length the driver doesn't track
+    pub(crate) fn fifo_frame_size(&self) -> Result<usize, MPU6050Error<BUS::Error>> {
+        let fifo = self.fifo_config;
+        let slaves =
+            fifo.is_slave0_enabled() || fifo.is_slave1_enabled() || fifo.is_slave2_enabled();
+        match fifo.frame_size() {
+            size if size == 0 || slaves => Err(MPU6050Error::InvalidConfig {
+                register: Registers::FifoEn.get_register_address(),
+                value: fifo.register_value(),
+            }),
+            size => Ok(size),
+        }
+    }
+
+    /// Decode one FIFO frame and apply the orientation
+    pub(crate) fn decode_fifo_frame(&self, bytes: &[u8]) -> FifoFrame {
+        let mut frame = FifoFrame::from_be_bytes(bytes, self.fifo_config);
+        frame.accel = frame.accel.map(|accel| self.orientation.apply(accel));
+        let fifo = self.fifo_config;
+        if fifo.is_gyro_x_enabled() && fifo.is_gyro_y_enabled() && fifo.is_gyro_z_enabled() {
+            frame.gyro = frame.gyro.map(|gyro| self.orientation.apply(gyro));
+        }
+        frame
+    }
+
+    /// Read three consecutive big-endian values in the sensor frame
+    pub(crate) fn read_vector(&mut self, register: Registers) -> Result<Vector3, MPU6050Error<BUS::Error>> {
         let mut buffer = [0u8; 6];
-        self.i2c.write_read(
-            self.address,
-            &[Registers::AccelXOutH.get_register_address()],
-            &mut buffer,
-        )?;
+        self.read_burst(register, &mut buffer)?;
 
         Ok(Vector3 {
             x: i16::from_be_bytes([buffer[0], buffer[1]]),
@@ -208,31 +623,274 @@ where
         })
     }
 
+    /// Reads raw accelerometer data for X, Y, and Z axes.
+    pub fn read_accel(&mut self) -> Result<Vector3, MPU6050Error<BUS::Error>> {
+        let accel = self.read_vector(Registers::AccelXOutH)?;
+        Ok(self.orientation.apply(accel))
+    }
+
     /// Reads raw gyroscope data for X, Y, and Z axes.
-    pub fn read_gyro(&mut self) -> Result<Vector3, MPU6050Error<I2C::Error>> {
-        let mut buffer = [0u8; 6];
-        self.i2c.write_read(
-            self.address,
-            &[Registers::GyroXOutH.get_register_address()],
-            &mut buffer,
-        )?;
+    pub fn read_gyro(&mut self) -> Result<Vector3, MPU6050Error<BUS::Error>> {
+        let gyro = self.read_vector(Registers::GyroXOutH)?;
+        Ok(self.orientation.apply(gyro))
+    }
 
-        Ok(Vector3 {
-            x: i16::from_be_bytes([buffer[0], buffer[1]]),
-            y: i16::from_be_bytes([buffer[2], buffer[3]]),
-            z: i16::from_be_bytes([buffer[4], buffer[5]]),
-        })
+    /// Reads accelerometer, temperature and gyroscope data in a single burst read.
+    pub fn read_sample(&mut self) -> Result<Sample, MPU6050Error<BUS::Error>> {
+        let mut buffer = [0u8; Sample::LEN];
+        self.read_burst(Registers::AccelXOutH, &mut buffer)?;
+        Ok(self.to_body_frame(Sample::from_be_bytes(buffer)))
     }
 
-    fn reg_value_to_temp(&self, buffer: [u8; 2]) -> i16 {
-        let raw_temp = i16::from_be_bytes(buffer);
-        ((raw_temp as f32) / 340.0 + 36.53) as i16
+    fn to_body_frame(&self, sample: Sample) -> Sample {
+        Sample {
+            accel: self.orientation.apply(sample.accel),
+            temp: sample.temp,
+            gyro: self.orientation.apply(sample.gyro),
+        }
     }
 
-    pub fn read_temp(&mut self) -> Result<i16, MPU6050Error<I2C::Error>> {
+    /// Get the FSYNC input configured through [`Self::configure_dlpf_and_ext_sync`]
+    /// or [`Self::apply_config`]
+    pub const fn ext_sync(&self) -> ExtSync {
+        self.ext_sync
+    }
+
+    /// Reads a sample and separates the FSYNC flag latched into the data LSB selected by
+    /// EXT_SYNC_SET. The flagged value has its LSB cleared.
+    /// With FSYNC disabled the sample is returned unchanged and `fsync` is `None`.
+    pub fn read_sample_fsync(&mut self) -> Result<FsyncSample, MPU6050Error<BUS::Error>> {
+        let mut buffer = [0u8; Sample::LEN];
+        self.read_burst(Registers::AccelXOutH, &mut buffer)?;
+        let mut sample = FsyncSample::from_be_bytes(buffer, self.ext_sync);
+        sample.sample = self.to_body_frame(sample.sample);
+        Ok(sample)
+    }
+
+    /// Read the FSYNC interrupt status (PASS_THROUGH bit of I2C_MST_STATUS).
+    ///
+    /// Only set when the FSYNC interrupt is enabled with
+    /// [`IntPinConfig::with_fsync_int_enabled`]. Reading I2C_MST_STATUS clears the bit.
+    pub fn read_fsync_interrupt_status(&mut self) -> Result<bool, MPU6050Error<BUS::Error>> {
+        let value = self.read_register(Registers::I2cMstStatus)?;
+        Ok(value & (1 << 7) != 0)
+    }
+
+    /// Reads the temperature sensor.
+    pub fn read_temperature(&mut self) -> Result<Temperature, MPU6050Error<BUS::Error>> {
         let mut buffer = [0u8; 2];
-        self.i2c.write_read(self.address, &[Registers::TempOutH.get_register_address()], &mut buffer)?;
-        let temp = self.reg_value_to_temp(buffer);
-        Ok(temp)
+        self.read_burst(Registers::TempOutH, &mut buffer)?;
+        Ok(Temperature::from_be_bytes(buffer))
+    }
+
+    /// Reads the raw TEMP_OUT register value.
+    pub fn read_temp_raw(&mut self) -> Result<i16, MPU6050Error<BUS::Error>> {
+        Ok(self.read_temperature()?.raw())
+    }
+
+    /// Reads the temperature in degrees Celsius, converted for the chip variant.
+    pub fn read_temp_celsius_f32(&mut self) -> Result<f32, MPU6050Error<BUS::Error>> {
+        Ok(self.read_temperature()?.celsius_f32_for(self.variant))
+    }
+
+    /// Reads the temperature in hundredths of a degree Celsius, converted for the chip variant.
+    /// Uses integer arithmetic only, no FPU required.
+    pub fn read_temp_centi_celsius(&mut self) -> Result<i32, MPU6050Error<BUS::Error>> {
+        Ok(self.read_temperature()?.centi_celsius_for(self.variant))
+    }
+
+    /// Reads the temperature in whole degrees Celsius, truncated towards zero.
+    pub fn read_temp(&mut self) -> Result<i16, MPU6050Error<BUS::Error>> {
+        Ok(self.read_temp_celsius_f32()? as i16)
+    }
+
+    /// Apply a complete device configuration.
+    ///
+    /// Registers are written in datasheet-safe order (power management and clock source first,
+    /// FIFO enable last) and every register is read back. The first register whose read-back value
+    /// differs from what was written is reported as [`MPU6050Error::VerificationMismatch`].
+    /// Nothing is written if [`DeviceConfig::validate`] fails.
+    pub fn apply_config(&mut self, config: &DeviceConfig) -> Result<(), MPU6050Error<BUS::Error>> {
+        config.validate()?;
+        self.check_accel_hpf(Some(config.accel_hpf))?;
+        // DEVICE_RESET clears itself, don't compare it
+        self.write_register_verified(Registers::PowerMgmt1, config.pwr_mgmt_1_value(), 0x7F)?;
+        self.write_register_verified(Registers::PowerMgmt2, config.pwr_mgmt_2.register_value(), 0xFF)?;
+        self.write_register_verified(Registers::SmprtDiv, config.sample_rate_divider, 0xFF)?;
+        self.write_register_verified(Registers::Config, config.config_value(), 0x3F)?;
+        self.ext_sync = config.ext_sync;
+        self.write_register_verified(Registers::GyroConfig, config.gyro.register_value(), 0xFF)?;
+        self.write_register_verified(Registers::AccelConfig, config.accel_config_value(), 0xFF)?;
+        self.write_register_verified(Registers::FifoEn, config.fifo.register_value(), 0xFF)?;
+        self.fifo_config = config.fifo;
+        self.write_register_verified(Registers::InterruptPinCfg, config.int_pin.register_value(), 0xFE)?;
+        self.write_register_verified(Registers::InterruptEnable, config.int_enable.register_value(), 0xFF)?;
+
+        let user_ctrl = self.read_register(Registers::UserCtrl)?;
+        let user_ctrl = if config.fifo_enabled {
+            user_ctrl | 1 << 6
+        } else {
+            user_ctrl & !(1 << 6)
+        };
+        // FIFO_RESET, I2C_MST_RESET and SIG_COND_RESET clear themselves, only FIFO_EN is compared
+        self.write_register_verified(Registers::UserCtrl, user_ctrl, 1 << 6)?;
+        Ok(())
+    }
+}
+
+impl<SPI> Mpu6050<SpiInterface<SPI>>
+where
+    SPI: embedded_hal::spi::SpiDevice,
+{
+    /// Create driver for an MPU-6000 on SPI, see [`SpiInterface`] for the clock speed limits
+    pub const fn new_spi(interface: SpiInterface<SPI>) -> Self {
+        Self::new(interface, 0)
+    }
+}
+
+impl<BUS> SampleSource for Mpu6050<BUS>
+where
+    BUS: RegisterBus,
+{
+    type Error = MPU6050Error<BUS::Error>;
+
+    fn read_sample(&mut self) -> Result<Sample, Self::Error> {
+        Mpu6050::read_sample(self)
+    }
+}
+
+/// Raise a transfer limit to [`MIN_TRANSFER_LEN`]
+const fn clamp_transfer_len(len: usize) -> usize {
+    if len < MIN_TRANSFER_LEN {
+        MIN_TRANSFER_LEN
+    } else {
+        len
+    }
+}
+
+#[cfg(all(test, feature = "sim"))]
+mod tests {
+    use embedded_hal::i2c::{ErrorType, I2c, Operation};
+
+    use super::*;
+    use crate::config::{DlpfConfig, FifoConfig};
+    use crate::sim::{SimError, SimulatedMpu6050};
+
+    /// Records the start register and length of every read transfer
+    struct Recorder {
+        sim: SimulatedMpu6050,
+        reads: [(u8, usize); 16],
+        count: usize,
+    }
+
+    impl Recorder {
+        fn new(sim: SimulatedMpu6050) -> Self {
+            Self {
+                sim,
+                reads: [(0, 0); 16],
+                count: 0,
+            }
+        }
+
+        fn reads(&self) -> &[(u8, usize)] {
+            &self.reads[..self.count]
+        }
+    }
+
+    impl ErrorType for Recorder {
+        type Error = SimError;
+    }
+
+    impl I2c for Recorder {
+        fn transaction(
+            &mut self,
+            address: SevenBitAddress,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            let mut register = 0;
+            for operation in operations.iter() {
+                match operation {
+                    Operation::Write(bytes) => register = bytes[0],
+                    Operation::Read(buffer) => {
+                        self.reads[self.count] = (register, buffer.len());
+                        self.count += 1;
+                    }
+                }
+            }
+            self.sim.transaction(address, operations)
+        }
+    }
+
+    fn awake_sim() -> SimulatedMpu6050 {
+        let mut sim = SimulatedMpu6050::default();
+        sim.set_accel_raw(1, 2, 3);
+        sim.set_gyro_raw(-4, 5, 6);
+        // Awake, PLL with X axis gyroscope reference
+        sim.poke(0x6B, 0x01);
+        sim.step(1);
+        sim
+    }
+
+    /// Driver with ten frames in the FIFO
+    fn filled_fifo(fifo: FifoConfig, max_transfer_len: usize) -> Mpu6050<Recorder> {
+        let config = DeviceConfig::new()
+            .with_dlpf(DlpfConfig::Dlpf1)
+            .with_sample_rate_divider(9)
+            .with_fifo(fifo, true);
+        let mut mpu = Mpu6050::new(awake_sim(), 0x68);
+        mpu.apply_config(&config).unwrap();
+        let mut sim = mpu.release();
+        sim.advance_us(100_000);
+        let mut mpu =
+            Mpu6050::new(Recorder::new(sim), 0x68).with_max_transfer_len(max_transfer_len);
+        mpu.configure_fifo(fifo).unwrap();
+        mpu
+    }
+
+    #[test]
+    fn long_reads_are_split() {
+        let mut expected = [0u8; 40];
+        Mpu6050::new(awake_sim(), 0x68)
+            .read_burst(Registers::AccelXOutH, &mut expected)
+            .unwrap();
+
+        let mut mpu = Mpu6050::new(Recorder::new(awake_sim()), 0x68).with_max_transfer_len(16);
+        let mut buffer = [0u8; 40];
+        mpu.read_burst(Registers::AccelXOutH, &mut buffer).unwrap();
+        assert_eq!(buffer, expected);
+        assert_eq!(mpu.release().reads(), [(0x3B, 16), (0x4B, 16), (0x5B, 8)]);
+    }
+
+    #[test]
+    fn fifo_reads_keep_whole_frames() {
+        let fifo = FifoConfig::new()
+            .enable_accel(true)
+            .enable_temp(true)
+            .enable_gyro_all(true);
+        let mut mpu = filled_fifo(fifo, 32);
+        let mut frames = [0u8; 140];
+        mpu.read_fifo(&mut frames).unwrap();
+        assert_eq!(mpu.release().reads(), [(0x74, 28); 5]);
+        for frame in frames.chunks(14) {
+            assert_eq!(frame[..6], [0, 1, 0, 2, 0, 3]);
+        }
+
+        let mut mpu = filled_fifo(FifoConfig::new().enable_accel(true), 32);
+        let mut frames = [0u8; 60];
+        mpu.read_fifo(&mut frames).unwrap();
+        assert_eq!(mpu.release().reads(), [(0x74, 30), (0x74, 30)]);
+    }
+
+    #[test]
+    fn transfer_limit_is_clamped_to_a_sample() {
+        let mut mpu = Mpu6050::new(Recorder::new(awake_sim()), 0x68).with_max_transfer_len(5);
+        assert_eq!(mpu.max_transfer_len(), 14);
+        mpu.set_max_transfer_len(0);
+        assert_eq!(mpu.max_transfer_len(), 14);
+
+        let sample = mpu.read_sample().unwrap();
+        assert_eq!(sample.accel, Vector3::new(1, 2, 3));
+        assert_eq!(sample.gyro, Vector3::new(-4, 5, 6));
+        assert_eq!(mpu.release().reads(), [(0x3B, 14)]);
     }
 }
@@ -0,0 +1,6 @@
+/// Round to nearest, halfway cases away from zero, and saturate to the i16 range.
+/// NaN becomes 0.
+pub(crate) fn round_to_i16(value: f32) -> i16 {
+    // `as` saturates at the i16 limits
+    libm::roundf(value) as i16
+}
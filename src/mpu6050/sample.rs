@@ -0,0 +1,43 @@
+use crate::mpu6050::{Temperature, Vector3};
+
+/// Accelerometer, temperature and gyroscope readings taken in a single burst read,
+/// so all values belong to the same sampling instant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sample {
+    pub accel: Vector3,
+    pub temp: Temperature,
+    pub gyro: Vector3,
+}
+
+impl Sample {
+    /// Number of bytes from ACCEL_XOUT_H to GYRO_ZOUT_L
+    pub const LEN: usize = 14;
+
+    /// Create from the register bytes ACCEL_XOUT_H to GYRO_ZOUT_L
+    pub const fn from_be_bytes(bytes: [u8; Self::LEN]) -> Self {
+        Self {
+            accel: Vector3 {
+                x: i16::from_be_bytes([bytes[0], bytes[1]]),
+                y: i16::from_be_bytes([bytes[2], bytes[3]]),
+                z: i16::from_be_bytes([bytes[4], bytes[5]]),
+            },
+            temp: Temperature::from_be_bytes([bytes[6], bytes[7]]),
+            gyro: Vector3 {
+                x: i16::from_be_bytes([bytes[8], bytes[9]]),
+                y: i16::from_be_bytes([bytes[10], bytes[11]]),
+                z: i16::from_be_bytes([bytes[12], bytes[13]]),
+            },
+        }
+    }
+}
+
+/// Anything that produces [`Sample`]s: the driver itself, or a replayed recording
+/// for testing algorithms without hardware
+pub trait SampleSource {
+    type Error;
+
+    /// Read the next sample
+    fn read_sample(&mut self) -> Result<Sample, Self::Error>;
+}
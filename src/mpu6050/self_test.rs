@@ -0,0 +1,165 @@
+use embedded_hal::delay::DelayNs;
+
+use crate::bus::RegisterBus;
+use crate::config::{AccelConfig, GyroConfig};
+use crate::errors::MPU6050Error;
+use crate::mpu6050::{Mpu6050, Vector3};
+use crate::registers::Registers;
+
+/// Largest allowed change from factory trim, as a fraction
+pub const SELF_TEST_TOLERANCE: f32 = 0.14;
+
+/// Time to let the outputs settle after changing self-test bits
+const SETTLE_MS: u32 = 50;
+
+/// Outcome of [`Mpu6050::self_test`]
+///
+/// Each value is the change of the self-test response from the factory trim value,
+/// `(response - trim) / trim`, for the X, Y and Z axes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelfTestResult {
+    pub accel: [f32; 3],
+    pub gyro: [f32; 3],
+}
+
+impl SelfTestResult {
+    /// Check if all axes are within [`SELF_TEST_TOLERANCE`] of factory trim
+    pub fn passed(&self) -> bool {
+        self.accel
+            .iter()
+            .chain(self.gyro.iter())
+            .all(|deviation| libm::fabsf(*deviation) <= SELF_TEST_TOLERANCE)
+    }
+
+    /// Return the result if all axes passed, otherwise [`MPU6050Error::SelfTestFailed`] with
+    /// the SELF_TEST_X, SELF_TEST_Y or SELF_TEST_Z register of the first failing axis
+    pub fn check<E>(self) -> Result<Self, MPU6050Error<E>> {
+        let registers = [Registers::SelfTestX, Registers::SelfTestY, Registers::SelfTestZ];
+        for (axis, register) in registers.iter().enumerate() {
+            let within = |deviation: f32| libm::fabsf(deviation) <= SELF_TEST_TOLERANCE;
+            if !within(self.accel[axis]) || !within(self.gyro[axis]) {
+                return Err(MPU6050Error::SelfTestFailed {
+                    register: register.get_register_address(),
+                });
+            }
+        }
+        Ok(self)
+    }
+}
+
+/// Factory trim of the gyroscope self-test response in LSB at ±250 °/s
+pub(crate) fn gyro_factory_trim(test: u8) -> f32 {
+    if test == 0 {
+        return 0.0;
+    }
+    25.0 * 131.0 * libm::powf(1.046, test as f32 - 1.0)
+}
+
+/// Factory trim of the accelerometer self-test response in LSB at ±8 g
+pub(crate) fn accel_factory_trim(test: u8) -> f32 {
+    if test == 0 {
+        return 0.0;
+    }
+    4096.0 * 0.34 * libm::powf(0.92 / 0.34, (test as f32 - 1.0) / 30.0)
+}
+
+/// Decode XA_TEST, YA_TEST, ZA_TEST and XG_TEST, YG_TEST, ZG_TEST
+/// from SELF_TEST_X, SELF_TEST_Y, SELF_TEST_Z and SELF_TEST_A
+pub(crate) fn decode_test_values(registers: [u8; 4]) -> ([u8; 3], [u8; 3]) {
+    let mut accel = [0u8; 3];
+    let mut gyro = [0u8; 3];
+    for axis in 0..3 {
+        accel[axis] = ((registers[axis] >> 3) & 0x1C) | ((registers[3] >> (4 - 2 * axis)) & 0x03);
+        gyro[axis] = registers[axis] & 0x1F;
+    }
+    (accel, gyro)
+}
+
+fn deviation(response: i32, trim: f32) -> f32 {
+    if trim == 0.0 {
+        return f32::INFINITY;
+    }
+    (response as f32 - trim) / trim
+}
+
+impl<BUS> Mpu6050<BUS>
+where
+    BUS: RegisterBus,
+{
+    /// Run the datasheet self-test.
+    ///
+    /// Measures the output change caused by enabling the self-test actuation at ±250 °/s and
+    /// ±8 g and compares it against the factory trim values in SELF_TEST_X..SELF_TEST_A.
+    /// The device must be awake and stationary. GYRO_CONFIG and ACCEL_CONFIG are restored
+    /// afterwards. Returns [`MPU6050Error::UnsupportedFeature`] on other family members, whose
+    /// factory trim is stored differently.
+    pub fn self_test(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<SelfTestResult, MPU6050Error<BUS::Error>> {
+        self.require(self.variant().has_self_test(), Registers::SelfTestX)?;
+        let gyro_config = self.read_register(Registers::GyroConfig)?;
+        let accel_config = self.read_register(Registers::AccelConfig)?;
+
+        let result = self.measure_self_test(delay);
+
+        self.write_register(Registers::GyroConfig, gyro_config)?;
+        self.write_register(Registers::AccelConfig, accel_config)?;
+        result
+    }
+
+    fn measure_self_test(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<SelfTestResult, MPU6050Error<BUS::Error>> {
+        const SELF_TEST_BITS: u8 = 0b1110_0000;
+
+        self.write_register(Registers::GyroConfig, GyroConfig::Dps250.register_value())?;
+        self.write_register(Registers::AccelConfig, AccelConfig::Range8G.register_value())?;
+        delay.delay_ms(SETTLE_MS);
+        let accel_off = self.read_vector(Registers::AccelXOutH)?;
+        let gyro_off = self.read_vector(Registers::GyroXOutH)?;
+
+        self.write_register(
+            Registers::GyroConfig,
+            GyroConfig::Dps250.register_value() | SELF_TEST_BITS,
+        )?;
+        self.write_register(
+            Registers::AccelConfig,
+            AccelConfig::Range8G.register_value() | SELF_TEST_BITS,
+        )?;
+        delay.delay_ms(SETTLE_MS);
+        let accel_on = self.read_vector(Registers::AccelXOutH)?;
+        let gyro_on = self.read_vector(Registers::GyroXOutH)?;
+
+        let mut registers = [0u8; 4];
+        self.read_registers(Registers::SelfTestX.get_register_address(), &mut registers)?;
+        let (accel_test, gyro_test) = decode_test_values(registers);
+
+        let response = |on: Vector3, off: Vector3| {
+            [
+                on.x as i32 - off.x as i32,
+                on.y as i32 - off.y as i32,
+                on.z as i32 - off.z as i32,
+            ]
+        };
+        let accel_response = response(accel_on, accel_off);
+        let gyro_response = response(gyro_on, gyro_off);
+
+        let mut result = SelfTestResult {
+            accel: [0.0; 3],
+            gyro: [0.0; 3],
+        };
+        for axis in 0..3 {
+            result.accel[axis] =
+                deviation(accel_response[axis], accel_factory_trim(accel_test[axis]));
+            // The Y axis gyroscope trim is negative
+            let gyro_trim = gyro_factory_trim(gyro_test[axis]);
+            let gyro_trim = if axis == 1 { -gyro_trim } else { gyro_trim };
+            result.gyro[axis] = deviation(gyro_response[axis], gyro_trim);
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,100 @@
+use crate::mpu6050::{round_to_i16, ChipVariant};
+
+/// Temperature sensor reading
+///
+/// Wraps the raw TEMP_OUT register value. Conversion follows the datasheet formula
+/// `°C = TEMP_OUT / 340 + 36.53`, the `*_for` methods use the formula of another family member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Temperature(i16);
+
+impl Temperature {
+    /// Create from raw TEMP_OUT register value
+    pub const fn from_raw(raw: i16) -> Self {
+        Self(raw)
+    }
+
+    /// Create from TEMP_OUT_H and TEMP_OUT_L register bytes
+    pub const fn from_be_bytes(bytes: [u8; 2]) -> Self {
+        Self(i16::from_be_bytes(bytes))
+    }
+
+    /// Create from a temperature in degrees Celsius, the inverse of [`Self::celsius_f32`].
+    /// Temperatures outside the TEMP_OUT range saturate.
+    pub fn from_celsius(celsius: f32) -> Self {
+        Self::from_celsius_for(celsius, ChipVariant::Mpu6050)
+    }
+
+    /// Create from a temperature in degrees Celsius for a family member, the inverse of
+    /// [`Self::celsius_f32_for`]
+    pub fn from_celsius_for(celsius: f32, variant: ChipVariant) -> Self {
+        let sensitivity = variant.temp_sensitivity_centi() as f32 / 100.0;
+        let offset = variant.temp_offset_centi() as f32 / 100.0;
+        Self(round_to_i16((celsius - offset) * sensitivity))
+    }
+
+    /// Get raw TEMP_OUT register value
+    pub const fn raw(&self) -> i16 {
+        self.0
+    }
+
+    /// Get temperature in hundredths of a degree Celsius, rounded to nearest.
+    /// Uses integer arithmetic only.
+    pub const fn centi_celsius(&self) -> i32 {
+        let scaled = self.0 as i32 * 100;
+        let rounded = if scaled >= 0 {
+            (scaled + 170) / 340
+        } else {
+            (scaled - 170) / 340
+        };
+        rounded + 3653
+    }
+
+    /// Get temperature in degrees Celsius
+    pub fn celsius_f32(&self) -> f32 {
+        (self.0 as f32) / 340.0 + 36.53
+    }
+
+    /// Get temperature in hundredths of a degree Celsius for a family member, rounded to
+    /// nearest. [`Self::centi_celsius`] is the MPU-6050 conversion.
+    pub const fn centi_celsius_for(&self, variant: ChipVariant) -> i32 {
+        let sensitivity = variant.temp_sensitivity_centi();
+        let scaled = self.0 as i32 * 10000;
+        let rounded = if scaled >= 0 {
+            (scaled + sensitivity / 2) / sensitivity
+        } else {
+            (scaled - sensitivity / 2) / sensitivity
+        };
+        rounded + variant.temp_offset_centi()
+    }
+
+    /// Get temperature in degrees Celsius for a family member
+    pub fn celsius_f32_for(&self, variant: ChipVariant) -> f32 {
+        let sensitivity = variant.temp_sensitivity_centi() as f32 / 100.0;
+        (self.0 as f32) / sensitivity + variant.temp_offset_centi() as f32 / 100.0
+    }
+
+    /// Get temperature in whole degrees Celsius, truncated towards zero
+    pub fn celsius(&self) -> i16 {
+        self.celsius_f32() as i16
+    }
+}
+
+impl From<i16> for Temperature {
+    fn from(raw: i16) -> Self {
+        Self::from_raw(raw)
+    }
+}
+
+impl From<Temperature> for i16 {
+    fn from(temp: Temperature) -> Self {
+        temp.raw()
+    }
+}
+
+impl From<Temperature> for f32 {
+    fn from(temp: Temperature) -> Self {
+        temp.celsius_f32()
+    }
+}
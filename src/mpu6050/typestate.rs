@@ -0,0 +1,377 @@
+//! Typestate wrapper around [`Mpu6050`]
+//!
+//! PWR_MGMT_1 resets with SLEEP set, and a sleeping device returns zeros from its data
+//! registers without any error. [`TypedMpu6050`] tracks the power state in its type and only
+//! offers reads in states where they produce valid data. State changes consume the driver and
+//! return it in the new state.
+
+use core::marker::PhantomData;
+
+use embedded_hal::delay::DelayNs;
+
+use crate::bus::RegisterBus;
+use crate::config::{
+    AccelConfig, AccelDlpf, AccelSettings, DlpfConfig, ExtSync, FifoConfig, GyroConfig,
+    IntPinConfig, InterruptEnable, LpWakeCtrl, Orientation, PwrMgmt1, PwrMgmt2,
+};
+use crate::errors::MPU6050Error;
+use crate::mpu6050::{FifoDrain, FifoFrame, FrameBuffer, FsyncSample, Mpu6050, Sample, Temperature, Vector3};
+use crate::registers::Registers;
+
+/// Device is in sleep mode, data registers are not updated
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct Sleeping;
+
+/// Device is awake, accelerometer, gyroscope and temperature are sampled
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct Awake;
+
+/// Low power accelerometer only mode, the device wakes up at the LP_WAKE_CTRL rate
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct CycleMode;
+
+/// Device is awake and writing samples into the FIFO
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct FifoStreaming;
+
+/// Failed state transition
+///
+/// Gives the driver back in its previous state together with the error.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct TransitionError<D, E> {
+    pub device: D,
+    pub error: MPU6050Error<E>,
+}
+
+/// [`Mpu6050`] with the power state tracked at compile time
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct TypedMpu6050<BUS, S> {
+    mpu: Mpu6050<BUS>,
+    _state: PhantomData<S>,
+}
+
+type Transition<BUS, From, To> = Result<
+    TypedMpu6050<BUS, To>,
+    TransitionError<TypedMpu6050<BUS, From>, <BUS as RegisterBus>::Error>,
+>;
+
+impl<BUS> Mpu6050<BUS>
+where
+    BUS: RegisterBus,
+{
+    /// Put the device to sleep and hand it over to the typestate API
+    pub fn into_sleeping(
+        mut self,
+    ) -> Result<TypedMpu6050<BUS, Sleeping>, TransitionError<Self, BUS::Error>> {
+        match self.update_pwr_mgmt_1(|pwr| pwr.set_sleep(true).set_cycle(false)) {
+            Ok(()) => Ok(TypedMpu6050::new(self)),
+            Err(error) => Err(TransitionError {
+                device: self,
+                error,
+            }),
+        }
+    }
+
+    pub(crate) fn update_pwr_mgmt_1(
+        &mut self,
+        update: impl FnOnce(PwrMgmt1) -> PwrMgmt1,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        let value = self.read_register(Registers::PowerMgmt1)?;
+        let value = update(PwrMgmt1::from_register(value)).device_reset(false);
+        self.write_register(Registers::PowerMgmt1, value.register_value())
+    }
+
+    fn update_pwr_mgmt_2(
+        &mut self,
+        update: impl FnOnce(PwrMgmt2) -> PwrMgmt2,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        let value = self.read_register(Registers::PowerMgmt2)?;
+        let value = update(PwrMgmt2::from_register(value));
+        self.write_register(Registers::PowerMgmt2, value.register_value())
+    }
+}
+
+impl<BUS, S> TypedMpu6050<BUS, S>
+where
+    BUS: RegisterBus,
+{
+    const fn new(mpu: Mpu6050<BUS>) -> Self {
+        Self {
+            mpu,
+            _state: PhantomData,
+        }
+    }
+
+    /// Run `operation` and move to state `T` if it succeeds
+    fn transition<T>(
+        mut self,
+        operation: impl FnOnce(&mut Mpu6050<BUS>) -> Result<(), MPU6050Error<BUS::Error>>,
+    ) -> Transition<BUS, S, T> {
+        match operation(&mut self.mpu) {
+            Ok(()) => Ok(TypedMpu6050::new(self.mpu)),
+            Err(error) => Err(TransitionError {
+                device: self,
+                error,
+            }),
+        }
+    }
+
+    /// Give up state tracking and return the plain driver
+    pub fn release(self) -> Mpu6050<BUS> {
+        self.mpu
+    }
+
+    /// Set the mounting orientation, see [`Mpu6050::set_orientation`]
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.mpu.set_orientation(orientation)
+    }
+
+    pub fn configure_gyro(&mut self, gyro_conf: GyroConfig) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.mpu.configure_gyro(gyro_conf)
+    }
+
+    pub fn configure_accel(
+        &mut self,
+        accel_conf: AccelConfig,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.mpu.configure_accel(accel_conf)
+    }
+
+    pub fn configure_accel_settings(
+        &mut self,
+        accel_settings: AccelSettings,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.mpu.configure_accel_settings(accel_settings)
+    }
+
+    pub fn configure_accel_dlpf(
+        &mut self,
+        accel_dlpf: AccelDlpf,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.mpu.configure_accel_dlpf(accel_dlpf)
+    }
+
+    pub fn configure_dlpf_and_ext_sync(
+        &mut self,
+        dlpf_config: DlpfConfig,
+        ext_sync: ExtSync,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.mpu.configure_dlpf_and_ext_sync(dlpf_config, ext_sync)
+    }
+
+    pub fn configure_sample_rate_divider(
+        &mut self,
+        divider: u8,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.mpu.configure_sample_rate_divider(divider)
+    }
+
+    pub fn configure_interrupt_enable(
+        &mut self,
+        interrupt_enable: InterruptEnable,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.mpu.configure_interrupt_enable(interrupt_enable)
+    }
+
+    pub fn configure_interrupt_pin(
+        &mut self,
+        interrupt_pin_conf: IntPinConfig,
+    ) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.mpu.configure_interrupt_pin(interrupt_pin_conf)
+    }
+}
+
+impl<BUS> TypedMpu6050<BUS, Sleeping>
+where
+    BUS: RegisterBus,
+{
+    /// Wake the device up using the X axis gyroscope PLL as clock source and wait for the
+    /// gyroscope start-up time, see [`Mpu6050::wake`].
+    pub fn wake(self, delay: &mut impl DelayNs) -> Transition<BUS, Sleeping, Awake> {
+        self.transition(|mpu| mpu.wake(delay))
+    }
+
+    /// Wake the device up and wait for the gyroscope to settle, see
+    /// [`Mpu6050::wake_discarding`].
+    pub fn wake_settled(
+        self,
+        delay: &mut impl DelayNs,
+        samples: u32,
+    ) -> Transition<BUS, Sleeping, Awake> {
+        self.transition(|mpu| mpu.wake_discarding(delay, samples))
+    }
+}
+
+impl<BUS> TypedMpu6050<BUS, Awake>
+where
+    BUS: RegisterBus,
+{
+    /// Put the device to sleep
+    pub fn sleep(self) -> Transition<BUS, Awake, Sleeping> {
+        self.transition(|mpu| mpu.update_pwr_mgmt_1(|pwr| pwr.set_sleep(true)))
+    }
+
+    /// Enter Low Power Accelerometer Only mode.
+    ///
+    /// As recommended by the datasheet, the temperature sensor is disabled and the
+    /// gyroscope axes are put into standby.
+    pub fn enter_cycle_mode(self, wake: LpWakeCtrl) -> Transition<BUS, Awake, CycleMode> {
+        self.transition(|mpu| {
+            mpu.update_pwr_mgmt_2(|pwr| pwr.with_lp_wake_ctrl(wake).with_standby_gyro_all(true))?;
+            mpu.update_pwr_mgmt_1(|pwr| {
+                pwr.set_sleep(false)
+                    .set_cycle(true)
+                    .disable_temp_sensor(true)
+            })
+        })
+    }
+
+    /// Configure which sensors are written to the FIFO, reset it and enable it
+    pub fn start_fifo(self, fifo_config: FifoConfig) -> Transition<BUS, Awake, FifoStreaming> {
+        self.transition(|mpu| {
+            mpu.configure_fifo(fifo_config)?;
+            mpu.reset_fifo()?;
+            mpu.set_fifo_enabled(true)
+        })
+    }
+
+    /// Reads raw accelerometer data for X, Y, and Z axes.
+    pub fn read_accel(&mut self) -> Result<Vector3, MPU6050Error<BUS::Error>> {
+        self.mpu.read_accel()
+    }
+
+    /// Reads raw gyroscope data for X, Y, and Z axes.
+    pub fn read_gyro(&mut self) -> Result<Vector3, MPU6050Error<BUS::Error>> {
+        self.mpu.read_gyro()
+    }
+
+    /// Reads accelerometer, temperature and gyroscope data in a single burst read.
+    pub fn read_sample(&mut self) -> Result<Sample, MPU6050Error<BUS::Error>> {
+        self.mpu.read_sample()
+    }
+
+    /// Reads a sample together with the FSYNC flag, see [`Mpu6050::read_sample_fsync`].
+    pub fn read_sample_fsync(&mut self) -> Result<FsyncSample, MPU6050Error<BUS::Error>> {
+        self.mpu.read_sample_fsync()
+    }
+
+    /// Read the FSYNC interrupt status.
+    pub fn read_fsync_interrupt_status(&mut self) -> Result<bool, MPU6050Error<BUS::Error>> {
+        self.mpu.read_fsync_interrupt_status()
+    }
+
+    /// Reads the temperature sensor.
+    pub fn read_temperature(&mut self) -> Result<Temperature, MPU6050Error<BUS::Error>> {
+        self.mpu.read_temperature()
+    }
+
+    pub fn read_temp(&mut self) -> Result<i16, MPU6050Error<BUS::Error>> {
+        self.mpu.read_temp()
+    }
+}
+
+impl<BUS> TypedMpu6050<BUS, CycleMode>
+where
+    BUS: RegisterBus,
+{
+    /// Leave Cycle mode, re-enabling the temperature sensor and the gyroscope axes
+    pub fn exit_cycle_mode(self) -> Transition<BUS, CycleMode, Awake> {
+        self.transition(|mpu| {
+            mpu.update_pwr_mgmt_1(|pwr| pwr.set_cycle(false).disable_temp_sensor(false))?;
+            mpu.update_pwr_mgmt_2(|pwr| pwr.with_standby_gyro_all(false))
+        })
+    }
+
+    /// Put the device to sleep
+    pub fn sleep(self) -> Transition<BUS, CycleMode, Sleeping> {
+        self.transition(|mpu| mpu.update_pwr_mgmt_1(|pwr| pwr.set_sleep(true).set_cycle(false)))
+    }
+
+    /// Reads raw accelerometer data for X, Y, and Z axes.
+    /// Only the accelerometer is sampled in Cycle mode.
+    pub fn read_accel(&mut self) -> Result<Vector3, MPU6050Error<BUS::Error>> {
+        self.mpu.read_accel()
+    }
+}
+
+impl<BUS> TypedMpu6050<BUS, FifoStreaming>
+where
+    BUS: RegisterBus,
+{
+    /// Disable the FIFO and stop writing sensors into it
+    pub fn stop_fifo(self) -> Transition<BUS, FifoStreaming, Awake> {
+        self.transition(|mpu| {
+            mpu.set_fifo_enabled(false)?;
+            mpu.configure_fifo(FifoConfig::new())
+        })
+    }
+
+    /// Reset the FIFO buffer.
+    pub fn reset_fifo(&mut self) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.mpu.reset_fifo()
+    }
+
+    /// Get current number of bytes in FIFO buffer.
+    pub fn get_fifo_count(&mut self) -> Result<u16, MPU6050Error<BUS::Error>> {
+        self.mpu.get_fifo_count()
+    }
+
+    /// Read data from FIFO buffer.
+    /// The buffer length determines how many bytes are read.
+    pub fn read_fifo(&mut self, buffer: &mut [u8]) -> Result<(), MPU6050Error<BUS::Error>> {
+        self.mpu.read_fifo(buffer)
+    }
+
+    /// Move all complete frames from the FIFO into `frames`, see [`Mpu6050::drain_fifo`].
+    pub fn drain_fifo(
+        &mut self,
+        frames: &mut impl FrameBuffer,
+    ) -> Result<FifoDrain, MPU6050Error<BUS::Error>> {
+        self.mpu.drain_fifo(frames)
+    }
+
+    /// Read the next frame from the FIFO, see [`Mpu6050::read_fifo_frame`].
+    pub fn read_fifo_frame(&mut self) -> Result<FifoFrame, MPU6050Error<BUS::Error>> {
+        self.mpu.read_fifo_frame()
+    }
+
+    /// Reads raw accelerometer data for X, Y, and Z axes.
+    pub fn read_accel(&mut self) -> Result<Vector3, MPU6050Error<BUS::Error>> {
+        self.mpu.read_accel()
+    }
+
+    /// Reads raw gyroscope data for X, Y, and Z axes.
+    pub fn read_gyro(&mut self) -> Result<Vector3, MPU6050Error<BUS::Error>> {
+        self.mpu.read_gyro()
+    }
+
+    /// Reads accelerometer, temperature and gyroscope data in a single burst read.
+    pub fn read_sample(&mut self) -> Result<Sample, MPU6050Error<BUS::Error>> {
+        self.mpu.read_sample()
+    }
+
+    /// Reads a sample together with the FSYNC flag, see [`Mpu6050::read_sample_fsync`].
+    pub fn read_sample_fsync(&mut self) -> Result<FsyncSample, MPU6050Error<BUS::Error>> {
+        self.mpu.read_sample_fsync()
+    }
+
+    /// Read the FSYNC interrupt status.
+    pub fn read_fsync_interrupt_status(&mut self) -> Result<bool, MPU6050Error<BUS::Error>> {
+        self.mpu.read_fsync_interrupt_status()
+    }
+
+    /// Reads the temperature sensor.
+    pub fn read_temperature(&mut self) -> Result<Temperature, MPU6050Error<BUS::Error>> {
+        self.mpu.read_temperature()
+    }
+
+    pub fn read_temp(&mut self) -> Result<i16, MPU6050Error<BUS::Error>> {
+        self.mpu.read_temp()
+    }
+}
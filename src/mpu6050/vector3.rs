@@ -0,0 +1,338 @@
+//! Three axis vector used for accelerometer and gyroscope data
+//!
+//! Raw readings are `Vector3<i16>` (the default type parameter). The arithmetic overflows on
+//! 16 bit values, so it is only available for the [`Arithmetic`] types: scale raw readings with
+//! [`Vector3::to_g`] or [`Vector3::to_dps`], or convert them with [`Vector3::widen`] or
+//! [`Vector3::to_f32`] first.
+
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::config::{AccelConfig, GyroConfig};
+
+/// Helper struct for returning 3D vector data (accel or gyro)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vector3<T = i16> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vector3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Apply `f` to every axis
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Vector3<U> {
+        Vector3 {
+            x: f(self.x),
+            y: f(self.y),
+            z: f(self.z),
+        }
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for i32 {}
+    impl Sealed for i64 {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+/// Axis types the vector arithmetic is implemented for
+///
+/// Sums of widened raw readings fit into `i32`, products are computed in [`Self::Product`]
+/// (`i64` for the integers) so a dot or cross product of full scale readings doesn't overflow.
+pub trait Arithmetic:
+    sealed::Sealed + Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// Type of products of two values
+    type Product: Copy + Add<Output = Self::Product> + Sub<Output = Self::Product>;
+
+    /// Multiply in the product type
+    fn product(self, other: Self) -> Self::Product;
+}
+
+impl Arithmetic for i32 {
+    type Product = i64;
+
+    fn product(self, other: Self) -> i64 {
+        i64::from(self) * i64::from(other)
+    }
+}
+
+impl Arithmetic for i64 {
+    type Product = i64;
+
+    fn product(self, other: Self) -> i64 {
+        self * other
+    }
+}
+
+impl Arithmetic for f32 {
+    type Product = f32;
+
+    fn product(self, other: Self) -> f32 {
+        self * other
+    }
+}
+
+impl Arithmetic for f64 {
+    type Product = f64;
+
+    fn product(self, other: Self) -> f64 {
+        self * other
+    }
+}
+
+impl<T: Arithmetic> Vector3<T> {
+    /// Multiply every axis by `factor`
+    pub fn scale(self, factor: T) -> Self {
+        self.map(|value| value * factor)
+    }
+
+    /// Dot product
+    pub fn dot(self, other: Self) -> T::Product {
+        self.x.product(other.x) + self.y.product(other.y) + self.z.product(other.z)
+    }
+
+    /// Cross product
+    pub fn cross(self, other: Self) -> Vector3<T::Product> {
+        Vector3 {
+            x: self.y.product(other.z) - self.z.product(other.y),
+            y: self.z.product(other.x) - self.x.product(other.z),
+            z: self.x.product(other.y) - self.y.product(other.x),
+        }
+    }
+
+    /// Squared Euclidean length, avoids the square root of [`Vector3::norm`]
+    pub fn norm_squared(self) -> T::Product {
+        self.dot(self)
+    }
+}
+
+impl Vector3<i16> {
+    /// Convert to `i32` for integer arithmetic
+    pub fn widen(self) -> Vector3<i32> {
+        self.map(i32::from)
+    }
+
+    /// Convert to `f32` without scaling
+    pub fn to_f32(self) -> Vector3<f32> {
+        self.map(f32::from)
+    }
+
+    /// Convert raw accelerometer output to g for the given full scale range
+    pub fn to_g(self, range: AccelConfig) -> Vector3<f32> {
+        let scale = range.scale_range() as f32 / 32768.0;
+        self.map(|value| value as f32 * scale)
+    }
+
+    /// Convert raw gyroscope output to °/s for the given full scale range
+    pub fn to_dps(self, range: GyroConfig) -> Vector3<f32> {
+        let scale = range.scale_range() as f32 / 32768.0;
+        self.map(|value| value as f32 * scale)
+    }
+}
+
+impl Vector3<f32> {
+    /// Euclidean length
+    pub fn norm(self) -> f32 {
+        libm::sqrtf(self.norm_squared())
+    }
+}
+
+impl Vector3<f64> {
+    /// Euclidean length
+    pub fn norm(self) -> f64 {
+        libm::sqrt(self.norm_squared())
+    }
+}
+
+impl<T: Arithmetic> Add for Vector3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl<T: Arithmetic> Sub for Vector3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl<T: Arithmetic + AddAssign> AddAssign for Vector3<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
+impl<T: Arithmetic + SubAssign> SubAssign for Vector3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+
+impl<T: Arithmetic + Neg<Output = T>> Neg for Vector3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.map(|value| -value)
+    }
+}
+
+/// Scale by a scalar
+impl<T: Arithmetic> Mul<T> for Vector3<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        self.map(|value| value * factor)
+    }
+}
+
+impl<T> From<[T; 3]> for Vector3<T> {
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Vector3<T>> for [T; 3] {
+    fn from(vector: Vector3<T>) -> Self {
+        [vector.x, vector.y, vector.z]
+    }
+}
+
+impl From<Vector3<i16>> for Vector3<i32> {
+    fn from(vector: Vector3<i16>) -> Self {
+        vector.map(i32::from)
+    }
+}
+
+impl From<Vector3<i16>> for Vector3<f32> {
+    fn from(vector: Vector3<i16>) -> Self {
+        vector.map(f32::from)
+    }
+}
+
+impl From<Vector3<i16>> for Vector3<f64> {
+    fn from(vector: Vector3<i16>) -> Self {
+        vector.map(f64::from)
+    }
+}
+
+impl From<Vector3<f32>> for Vector3<f64> {
+    fn from(vector: Vector3<f32>) -> Self {
+        vector.map(f64::from)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T> From<mint::Vector3<T>> for Vector3<T> {
+    fn from(vector: mint::Vector3<T>) -> Self {
+        Self::new(vector.x, vector.y, vector.z)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T> From<Vector3<T>> for mint::Vector3<T> {
+    fn from(vector: Vector3<T>) -> Self {
+        mint::Vector3 {
+            x: vector.x,
+            y: vector.y,
+            z: vector.z,
+        }
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar> From<nalgebra::Vector3<T>> for Vector3<T> {
+    fn from(vector: nalgebra::Vector3<T>) -> Self {
+        let [x, y, z]: [T; 3] = vector.into();
+        Self::new(x, y, z)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar> From<Vector3<T>> for nalgebra::Vector3<T> {
+    fn from(vector: Vector3<T>) -> Self {
+        nalgebra::Vector3::new(vector.x, vector.y, vector.z)
+    }
+}
+
+#[cfg(feature = "micromath")]
+impl<C: micromath::vector::Component> From<micromath::vector::Vector3d<C>> for Vector3<C> {
+    fn from(vector: micromath::vector::Vector3d<C>) -> Self {
+        Self::new(vector.x, vector.y, vector.z)
+    }
+}
+
+#[cfg(feature = "micromath")]
+impl<C: micromath::vector::Component> From<Vector3<C>> for micromath::vector::Vector3d<C> {
+    fn from(vector: Vector3<C>) -> Self {
+        micromath::vector::Vector3d {
+            x: vector.x,
+            y: vector.y,
+            z: vector.z,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL_SCALE: Vector3 = Vector3::new(i16::MIN, i16::MIN, i16::MAX);
+
+    #[test]
+    fn widened_products_do_not_overflow() {
+        let v = FULL_SCALE.widen();
+        // Beyond i32::MAX
+        assert_eq!(v.norm_squared(), 3_221_159_937);
+        assert_eq!(v.dot(v), v.norm_squared());
+        assert_eq!(v.cross(v), Vector3::new(0, 0, 0));
+        let w = Vector3::new(i16::MAX, i16::MIN, i16::MIN).widen();
+        let cross = v.cross(w);
+        assert_eq!(cross.dot(v.map(i64::from)), 0);
+        assert_eq!(cross.dot(w.map(i64::from)), 0);
+        assert_eq!(v + v, Vector3::new(-65536, -65536, 65534));
+        assert_eq!(v - w, Vector3::new(-65535, 0, 65535));
+        assert_eq!(v * 2, v.scale(2));
+        assert_eq!(-v, Vector3::new(32768, 32768, -32767));
+    }
+
+    #[test]
+    fn float_arithmetic() {
+        let x = Vector3::new(1.0f32, 0.0, 0.0);
+        let y = Vector3::new(0.0f32, 1.0, 0.0);
+        assert_eq!(x.cross(y), Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(x.dot(y), 0.0);
+        assert_eq!(Vector3::new(3.0f32, 4.0, 12.0).norm(), 13.0);
+        assert_eq!(Vector3::new(3.0f64, 4.0, 12.0).norm(), 13.0);
+
+        let mut v = Vector3::new(300i16, -200, 100).to_f32();
+        v += x;
+        v -= y * 2.0;
+        assert_eq!(v, Vector3::new(301.0, -202.0, 100.0));
+        assert_eq!(v.scale(0.5), Vector3::new(150.5, -101.0, 50.0));
+    }
+}
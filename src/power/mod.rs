@@ -0,0 +1,4 @@
+
+mod power_estimate;
+
+pub use power_estimate::{PowerEstimate, PowerMode};
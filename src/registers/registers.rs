@@ -1,20 +1,46 @@
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
 #[repr(u8)]
 pub enum Registers{
+    XaOffsUsrH = 0x06,
+    YaOffsUsrH = 0x08,
+    ZaOffsUsrH = 0x0A,
+    SelfTestX = 0x0D,
+    SelfTestY = 0x0E,
+    SelfTestZ = 0x0F,
+    SelfTestA = 0x10,
+    XgOffsUsrH = 0x13,
+    YgOffsUsrH = 0x15,
+    ZgOffsUsrH = 0x17,
     SmprtDiv = 0x19,
     Config = 0x1A,
     GyroConfig = 0x1B,
     AccelConfig = 0x1C,
+    AccelConfig2 = 0x1D,
+    MotThr = 0x1F,
     FifoEn = 0x23,
+    I2cMstCtrl = 0x24,
+    I2cSlv0Addr = 0x25,
+    I2cSlv0Reg = 0x26,
+    I2cSlv0Ctrl = 0x27,
+    I2cSlv1Addr = 0x28,
+    I2cSlv1Reg = 0x29,
+    I2cSlv1Ctrl = 0x2A,
+    I2cSlv2Addr = 0x2B,
+    I2cSlv2Reg = 0x2C,
+    I2cSlv2Ctrl = 0x2D,
+    I2cSlv3Addr = 0x2E,
+    I2cSlv3Reg = 0x2F,
+    I2cSlv3Ctrl = 0x30,
+    I2cSlv4Addr = 0x31,
+    I2cSlv4Reg = 0x32,
+    I2cSlv4Do = 0x33,
+    I2cSlv4Ctrl = 0x34,
+    I2cSlv4Di = 0x35,
+    I2cMstStatus = 0x36,
     InterruptPinCfg = 0x37,
     InterruptEnable = 0x38,
     InterruptStatus = 0x3A,
-    UserCtrl = 0x6A,
-    FifoCountH = 0x72,
-    FifoCountL = 0x73,
-    FifoRW = 0x74,
-    WhoAmI = 0x75,
-    PowerMgmt1 = 0x6B,
     AccelXOutH = 0x3B,
     AccelXOutL = 0x3C,
     AccelYOutH = 0x3D,
@@ -28,9 +54,244 @@ pub enum Registers{
     GyroYOutH = 0x45,
     GyroYOutL = 0x46,
     GyroZOutH = 0x47,
-    GyroZOutL = 0x48
+    GyroZOutL = 0x48,
+    ExtSensData00 = 0x49,
+    ExtSensData01 = 0x4A,
+    ExtSensData02 = 0x4B,
+    ExtSensData03 = 0x4C,
+    ExtSensData04 = 0x4D,
+    ExtSensData05 = 0x4E,
+    ExtSensData06 = 0x4F,
+    ExtSensData07 = 0x50,
+    ExtSensData08 = 0x51,
+    ExtSensData09 = 0x52,
+    ExtSensData10 = 0x53,
+    ExtSensData11 = 0x54,
+    ExtSensData12 = 0x55,
+    ExtSensData13 = 0x56,
+    ExtSensData14 = 0x57,
+    ExtSensData15 = 0x58,
+    ExtSensData16 = 0x59,
+    ExtSensData17 = 0x5A,
+    ExtSensData18 = 0x5B,
+    ExtSensData19 = 0x5C,
+    ExtSensData20 = 0x5D,
+    ExtSensData21 = 0x5E,
+    ExtSensData22 = 0x5F,
+    ExtSensData23 = 0x60,
+    I2cSlv0Do = 0x63,
+    I2cSlv1Do = 0x64,
+    I2cSlv2Do = 0x65,
+    I2cSlv3Do = 0x66,
+    I2cMstDelayCtrl = 0x67,
+    SignalPathReset = 0x68,
+    MotDetectCtrl = 0x69,
+    UserCtrl = 0x6A,
+    PowerMgmt1 = 0x6B,
+    PowerMgmt2 = 0x6C,
+    FifoCountH = 0x72,
+    FifoCountL = 0x73,
+    FifoRW = 0x74,
+    WhoAmI = 0x75,
+    XaOffsetH = 0x77,
+    YaOffsetH = 0x7A,
+    ZaOffsetH = 0x7D,
 }
 
 impl Registers {
+    /// All documented MPU-6050 registers in ascending address order.
+    /// The offset registers and the registers of other family members are not included.
+    pub const ALL: [Registers; 84] = [
+        Self::SelfTestX,
+        Self::SelfTestY,
+        Self::SelfTestZ,
+        Self::SelfTestA,
+        Self::SmprtDiv,
+        Self::Config,
+        Self::GyroConfig,
+        Self::AccelConfig,
+        Self::MotThr,
+        Self::FifoEn,
+        Self::I2cMstCtrl,
+        Self::I2cSlv0Addr,
+        Self::I2cSlv0Reg,
+        Self::I2cSlv0Ctrl,
+        Self::I2cSlv1Addr,
+        Self::I2cSlv1Reg,
+        Self::I2cSlv1Ctrl,
+        Self::I2cSlv2Addr,
+        Self::I2cSlv2Reg,
+        Self::I2cSlv2Ctrl,
+        Self::I2cSlv3Addr,
+        Self::I2cSlv3Reg,
+        Self::I2cSlv3Ctrl,
+        Self::I2cSlv4Addr,
+        Self::I2cSlv4Reg,
+        Self::I2cSlv4Do,
+        Self::I2cSlv4Ctrl,
+        Self::I2cSlv4Di,
+        Self::I2cMstStatus,
+        Self::InterruptPinCfg,
+        Self::InterruptEnable,
+        Self::InterruptStatus,
+        Self::AccelXOutH,
+        Self::AccelXOutL,
+        Self::AccelYOutH,
+        Self::AccelYOutL,
+        Self::AccelZOutH,
+        Self::AccelZOutL,
+        Self::TempOutH,
+        Self::TempOutL,
+        Self::GyroXOutH,
+        Self::GyroXOutL,
+        Self::GyroYOutH,
+        Self::GyroYOutL,
+        Self::GyroZOutH,
+        Self::GyroZOutL,
+        Self::ExtSensData00,
+        Self::ExtSensData01,
+        Self::ExtSensData02,
+        Self::ExtSensData03,
+        Self::ExtSensData04,
+        Self::ExtSensData05,
+        Self::ExtSensData06,
+        Self::ExtSensData07,
+        Self::ExtSensData08,
+        Self::ExtSensData09,
+        Self::ExtSensData10,
+        Self::ExtSensData11,
+        Self::ExtSensData12,
+        Self::ExtSensData13,
+        Self::ExtSensData14,
+        Self::ExtSensData15,
+        Self::ExtSensData16,
+        Self::ExtSensData17,
+        Self::ExtSensData18,
+        Self::ExtSensData19,
+        Self::ExtSensData20,
+        Self::ExtSensData21,
+        Self::ExtSensData22,
+        Self::ExtSensData23,
+        Self::I2cSlv0Do,
+        Self::I2cSlv1Do,
+        Self::I2cSlv2Do,
+        Self::I2cSlv3Do,
+        Self::I2cMstDelayCtrl,
+        Self::SignalPathReset,
+        Self::MotDetectCtrl,
+        Self::UserCtrl,
+        Self::PowerMgmt1,
+        Self::PowerMgmt2,
+        Self::FifoCountH,
+        Self::FifoCountL,
+        Self::FifoRW,
+        Self::WhoAmI,
+    ];
+
     pub fn get_register_address(&self) -> u8 {*self as u8}
-}
\ No newline at end of file
+
+    /// Look up register by address
+    pub fn from_address(address: u8) -> Option<Self> {
+        Self::ALL.iter().copied().find(|register| register.get_register_address() == address)
+    }
+
+    /// Register name as used in the datasheet
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::XaOffsUsrH => "XA_OFFS_USRH",
+            Self::YaOffsUsrH => "YA_OFFS_USRH",
+            Self::ZaOffsUsrH => "ZA_OFFS_USRH",
+            Self::XgOffsUsrH => "XG_OFFS_USRH",
+            Self::YgOffsUsrH => "YG_OFFS_USRH",
+            Self::ZgOffsUsrH => "ZG_OFFS_USRH",
+            Self::AccelConfig2 => "ACCEL_CONFIG2",
+            Self::XaOffsetH => "XA_OFFSET_H",
+            Self::YaOffsetH => "YA_OFFSET_H",
+            Self::ZaOffsetH => "ZA_OFFSET_H",
+            Self::SelfTestX => "SELF_TEST_X",
+            Self::SelfTestY => "SELF_TEST_Y",
+            Self::SelfTestZ => "SELF_TEST_Z",
+            Self::SelfTestA => "SELF_TEST_A",
+            Self::SmprtDiv => "SMPLRT_DIV",
+            Self::Config => "CONFIG",
+            Self::GyroConfig => "GYRO_CONFIG",
+            Self::AccelConfig => "ACCEL_CONFIG",
+            Self::MotThr => "MOT_THR",
+            Self::FifoEn => "FIFO_EN",
+            Self::I2cMstCtrl => "I2C_MST_CTRL",
+            Self::I2cSlv0Addr => "I2C_SLV0_ADDR",
+            Self::I2cSlv0Reg => "I2C_SLV0_REG",
+            Self::I2cSlv0Ctrl => "I2C_SLV0_CTRL",
+            Self::I2cSlv1Addr => "I2C_SLV1_ADDR",
+            Self::I2cSlv1Reg => "I2C_SLV1_REG",
+            Self::I2cSlv1Ctrl => "I2C_SLV1_CTRL",
+            Self::I2cSlv2Addr => "I2C_SLV2_ADDR",
+            Self::I2cSlv2Reg => "I2C_SLV2_REG",
+            Self::I2cSlv2Ctrl => "I2C_SLV2_CTRL",
+            Self::I2cSlv3Addr => "I2C_SLV3_ADDR",
+            Self::I2cSlv3Reg => "I2C_SLV3_REG",
+            Self::I2cSlv3Ctrl => "I2C_SLV3_CTRL",
+            Self::I2cSlv4Addr => "I2C_SLV4_ADDR",
+            Self::I2cSlv4Reg => "I2C_SLV4_REG",
+            Self::I2cSlv4Do => "I2C_SLV4_DO",
+            Self::I2cSlv4Ctrl => "I2C_SLV4_CTRL",
+            Self::I2cSlv4Di => "I2C_SLV4_DI",
+            Self::I2cMstStatus => "I2C_MST_STATUS",
+            Self::InterruptPinCfg => "INT_PIN_CFG",
+            Self::InterruptEnable => "INT_ENABLE",
+            Self::InterruptStatus => "INT_STATUS",
+            Self::AccelXOutH => "ACCEL_XOUT_H",
+            Self::AccelXOutL => "ACCEL_XOUT_L",
+            Self::AccelYOutH => "ACCEL_YOUT_H",
+            Self::AccelYOutL => "ACCEL_YOUT_L",
+            Self::AccelZOutH => "ACCEL_ZOUT_H",
+            Self::AccelZOutL => "ACCEL_ZOUT_L",
+            Self::TempOutH => "TEMP_OUT_H",
+            Self::TempOutL => "TEMP_OUT_L",
+            Self::GyroXOutH => "GYRO_XOUT_H",
+            Self::GyroXOutL => "GYRO_XOUT_L",
+            Self::GyroYOutH => "GYRO_YOUT_H",
+            Self::GyroYOutL => "GYRO_YOUT_L",
+            Self::GyroZOutH => "GYRO_ZOUT_H",
+            Self::GyroZOutL => "GYRO_ZOUT_L",
+            Self::ExtSensData00 => "EXT_SENS_DATA_00",
+            Self::ExtSensData01 => "EXT_SENS_DATA_01",
+            Self::ExtSensData02 => "EXT_SENS_DATA_02",
+            Self::ExtSensData03 => "EXT_SENS_DATA_03",
+            Self::ExtSensData04 => "EXT_SENS_DATA_04",
+            Self::ExtSensData05 => "EXT_SENS_DATA_05",
+            Self::ExtSensData06 => "EXT_SENS_DATA_06",
+            Self::ExtSensData07 => "EXT_SENS_DATA_07",
+            Self::ExtSensData08 => "EXT_SENS_DATA_08",
+            Self::ExtSensData09 => "EXT_SENS_DATA_09",
+            Self::ExtSensData10 => "EXT_SENS_DATA_10",
+            Self::ExtSensData11 => "EXT_SENS_DATA_11",
+            Self::ExtSensData12 => "EXT_SENS_DATA_12",
+            Self::ExtSensData13 => "EXT_SENS_DATA_13",
+            Self::ExtSensData14 => "EXT_SENS_DATA_14",
+            Self::ExtSensData15 => "EXT_SENS_DATA_15",
+            Self::ExtSensData16 => "EXT_SENS_DATA_16",
+            Self::ExtSensData17 => "EXT_SENS_DATA_17",
+            Self::ExtSensData18 => "EXT_SENS_DATA_18",
+            Self::ExtSensData19 => "EXT_SENS_DATA_19",
+            Self::ExtSensData20 => "EXT_SENS_DATA_20",
+            Self::ExtSensData21 => "EXT_SENS_DATA_21",
+            Self::ExtSensData22 => "EXT_SENS_DATA_22",
+            Self::ExtSensData23 => "EXT_SENS_DATA_23",
+            Self::I2cSlv0Do => "I2C_SLV0_DO",
+            Self::I2cSlv1Do => "I2C_SLV1_DO",
+            Self::I2cSlv2Do => "I2C_SLV2_DO",
+            Self::I2cSlv3Do => "I2C_SLV3_DO",
+            Self::I2cMstDelayCtrl => "I2C_MST_DELAY_CTRL",
+            Self::SignalPathReset => "SIGNAL_PATH_RESET",
+            Self::MotDetectCtrl => "MOT_DETECT_CTRL",
+            Self::UserCtrl => "USER_CTRL",
+            Self::PowerMgmt1 => "PWR_MGMT_1",
+            Self::PowerMgmt2 => "PWR_MGMT_2",
+            Self::FifoCountH => "FIFO_COUNTH",
+            Self::FifoCountL => "FIFO_COUNTL",
+            Self::FifoRW => "FIFO_R_W",
+            Self::WhoAmI => "WHO_AM_I",
+        }
+    }
+}
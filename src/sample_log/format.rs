@@ -0,0 +1,229 @@
+//! Binary sample log format
+//!
+//! A log is a [`LogHeader`] followed by any number of [`LogRecord`]s. All multi-byte values
+//! are little-endian. Samples are stored raw and uncalibrated; the header carries everything
+//! needed to scale them back to physical units.
+//!
+//! Header, [`HEADER_LEN`] bytes:
+//!
+//! | offset | size | field |
+//! |--------|------|-------|
+//! | 0      | 8    | magic `MPU6LOG\0` |
+//! | 8      | 1    | format version |
+//! | 9      | 1    | WHO_AM_I |
+//! | 10     | 1    | FS_SEL (gyroscope range) |
+//! | 11     | 1    | AFS_SEL (accelerometer range) |
+//! | 12     | 1    | DLPF_CFG |
+//! | 13     | 1    | SMPLRT_DIV |
+//! | 14     | 2    | reserved, 0 |
+//! | 16     | 6    | accelerometer offsets X, Y, Z (i16) |
+//! | 22     | 6    | gyroscope offsets X, Y, Z (i16) |
+//!
+//! Record, [`RECORD_LEN`] bytes: timestamp in µs (u64), accelerometer X, Y, Z,
+//! TEMP_OUT, gyroscope X, Y, Z (i16).
+
+use core::fmt;
+
+use crate::compensation::Calibration;
+use crate::config::{AccelConfig, DeviceConfig, DlpfConfig, GyroConfig};
+use crate::mpu6050::{ChipVariant, Sample, Temperature, Vector3};
+
+const MAGIC: [u8; 8] = *b"MPU6LOG\0";
+const VERSION: u8 = 1;
+
+/// Length of an encoded header in bytes
+pub const HEADER_LEN: usize = 28;
+/// Length of an encoded record in bytes
+pub const RECORD_LEN: usize = 22;
+
+/// Error decoding a log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub enum LogFormatError {
+    /// Data does not start with the log magic
+    BadMagic,
+    /// Log was written by a newer format version
+    UnsupportedVersion(u8),
+    /// Not enough bytes
+    Truncated,
+    /// Header field holds a reserved value
+    InvalidField,
+}
+
+impl fmt::Display for LogFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "Not a sample log"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "Unsupported log format version {}", version)
+            }
+            Self::Truncated => write!(f, "Log data truncated"),
+            Self::InvalidField => write!(f, "Invalid header field"),
+        }
+    }
+}
+
+impl core::error::Error for LogFormatError {}
+
+/// Log header describing the configuration the samples were recorded with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct LogHeader {
+    pub who_am_i: u8,
+    pub gyro: GyroConfig,
+    pub accel: AccelConfig,
+    pub dlpf: DlpfConfig,
+    pub sample_rate_divider: u8,
+    pub calibration: Calibration,
+}
+
+impl LogHeader {
+    /// Create header for samples recorded with `config`
+    pub const fn from_config(config: &DeviceConfig, who_am_i: u8, calibration: Calibration) -> Self {
+        Self {
+            who_am_i,
+            gyro: config.gyro,
+            accel: config.accel,
+            dlpf: config.dlpf,
+            sample_rate_divider: config.sample_rate_divider,
+            calibration,
+        }
+    }
+
+    /// Sample rate in Hz the log was recorded at
+    pub const fn sample_rate_hz(&self) -> u32 {
+        self.dlpf.gyro_output_rate_hz() / (1 + self.sample_rate_divider as u32)
+    }
+
+    /// Encode header
+    pub fn encode(&self) -> [u8; HEADER_LEN] {
+        let mut bytes = [0u8; HEADER_LEN];
+        bytes[0..8].copy_from_slice(&MAGIC);
+        bytes[8] = VERSION;
+        bytes[9] = self.who_am_i;
+        bytes[10] = self.gyro as u8;
+        bytes[11] = self.accel as u8;
+        bytes[12] = self.dlpf.register_value();
+        bytes[13] = self.sample_rate_divider;
+        write_vector(&mut bytes[16..22], self.calibration.accel_offset);
+        write_vector(&mut bytes[22..28], self.calibration.gyro_offset);
+        bytes
+    }
+
+    /// Decode header from the start of `bytes`
+    pub fn decode(bytes: &[u8]) -> Result<Self, LogFormatError> {
+        if bytes.len() < HEADER_LEN {
+            return Err(LogFormatError::Truncated);
+        }
+        if bytes[0..8] != MAGIC {
+            return Err(LogFormatError::BadMagic);
+        }
+        if bytes[8] > VERSION {
+            return Err(LogFormatError::UnsupportedVersion(bytes[8]));
+        }
+        if bytes[10] > 3 || bytes[11] > 3 {
+            return Err(LogFormatError::InvalidField);
+        }
+        let dlpf = DlpfConfig::from_register(bytes[12]).ok_or(LogFormatError::InvalidField)?;
+        Ok(Self {
+            who_am_i: bytes[9],
+            gyro: GyroConfig::from_register(bytes[10] << 3),
+            accel: AccelConfig::from_register(bytes[11] << 3),
+            dlpf,
+            sample_rate_divider: bytes[13],
+            calibration: Calibration::new()
+                .with_accel_offset(read_vector(&bytes[16..22]))
+                .with_gyro_offset(read_vector(&bytes[22..28])),
+        })
+    }
+
+    /// Apply calibration and convert a record to physical units.
+    /// The temperature is converted with the formula of the family member identified by
+    /// WHO_AM_I, unknown values use the MPU-6050 formula.
+    pub fn scale(&self, record: &LogRecord) -> ScaledSample {
+        let sample = self.calibration.apply(record.sample);
+        let variant = ChipVariant::from_who_am_i(self.who_am_i).unwrap_or_default();
+        let accel_scale = self.accel.scale_range() as f32 / 32768.0;
+        let gyro_scale = self.gyro.scale_range() as f32 / 32768.0;
+        ScaledSample {
+            timestamp_us: record.timestamp_us,
+            accel_g: [
+                sample.accel.x as f32 * accel_scale,
+                sample.accel.y as f32 * accel_scale,
+                sample.accel.z as f32 * accel_scale,
+            ],
+            gyro_dps: [
+                sample.gyro.x as f32 * gyro_scale,
+                sample.gyro.y as f32 * gyro_scale,
+                sample.gyro.z as f32 * gyro_scale,
+            ],
+            temp_c: sample.temp.celsius_f32_for(variant),
+        }
+    }
+}
+
+/// Timestamped raw sample
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogRecord {
+    pub timestamp_us: u64,
+    pub sample: Sample,
+}
+
+impl LogRecord {
+    /// Encode record
+    pub fn encode(&self) -> [u8; RECORD_LEN] {
+        let mut bytes = [0u8; RECORD_LEN];
+        bytes[0..8].copy_from_slice(&self.timestamp_us.to_le_bytes());
+        write_vector(&mut bytes[8..14], self.sample.accel);
+        bytes[14..16].copy_from_slice(&self.sample.temp.raw().to_le_bytes());
+        write_vector(&mut bytes[16..22], self.sample.gyro);
+        bytes
+    }
+
+    /// Decode record from the start of `bytes`
+    pub fn decode(bytes: &[u8]) -> Result<Self, LogFormatError> {
+        if bytes.len() < RECORD_LEN {
+            return Err(LogFormatError::Truncated);
+        }
+        let mut timestamp = [0u8; 8];
+        timestamp.copy_from_slice(&bytes[0..8]);
+        Ok(Self {
+            timestamp_us: u64::from_le_bytes(timestamp),
+            sample: Sample {
+                accel: read_vector(&bytes[8..14]),
+                temp: Temperature::from_raw(i16::from_le_bytes([bytes[14], bytes[15]])),
+                gyro: read_vector(&bytes[16..22]),
+            },
+        })
+    }
+}
+
+/// Sample converted to physical units
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScaledSample {
+    pub timestamp_us: u64,
+    /// Acceleration in g
+    pub accel_g: [f32; 3],
+    /// Angular rate in °/s
+    pub gyro_dps: [f32; 3],
+    /// Temperature in °C
+    pub temp_c: f32,
+}
+
+fn write_vector(bytes: &mut [u8], vector: Vector3) {
+    bytes[0..2].copy_from_slice(&vector.x.to_le_bytes());
+    bytes[2..4].copy_from_slice(&vector.y.to_le_bytes());
+    bytes[4..6].copy_from_slice(&vector.z.to_le_bytes());
+}
+
+fn read_vector(bytes: &[u8]) -> Vector3 {
+    Vector3 {
+        x: i16::from_le_bytes([bytes[0], bytes[1]]),
+        y: i16::from_le_bytes([bytes[2], bytes[3]]),
+        z: i16::from_le_bytes([bytes[4], bytes[5]]),
+    }
+}
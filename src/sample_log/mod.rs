@@ -0,0 +1,11 @@
+
+pub mod format;
+#[cfg(feature = "std")]
+pub mod reader;
+#[cfg(feature = "std")]
+pub mod replay;
+pub use format::{LogFormatError, LogHeader, LogRecord, ScaledSample, HEADER_LEN, RECORD_LEN};
+#[cfg(feature = "std")]
+pub use reader::{LogReader, LogRecords};
+#[cfg(feature = "std")]
+pub use replay::{CsvRecords, Pacing, Replay, ReplayError};
@@ -0,0 +1,97 @@
+use std::io::{self, Read};
+
+use crate::sample_log::format::{LogHeader, LogRecord, ScaledSample, HEADER_LEN, RECORD_LEN};
+
+/// Reads a sample log, e.g. from a file
+///
+/// Iterating yields samples scaled with the ranges and calibration from the header.
+/// A partial record at the end of the log, as left by a power loss during recording,
+/// is ignored.
+pub struct LogReader<R> {
+    reader: R,
+    header: LogHeader,
+}
+
+impl<R> LogReader<R>
+where
+    R: Read,
+{
+    /// Read and validate the header
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut bytes = [0u8; HEADER_LEN];
+        reader.read_exact(&mut bytes)?;
+        let header = LogHeader::decode(&bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Self { reader, header })
+    }
+
+    /// Get log header
+    pub fn header(&self) -> &LogHeader {
+        &self.header
+    }
+
+    /// Read the next raw record, `None` at the end of the log
+    pub fn next_record(&mut self) -> io::Result<Option<LogRecord>> {
+        let mut bytes = [0u8; RECORD_LEN];
+        let mut filled = 0;
+        while filled < RECORD_LEN {
+            match self.reader.read(&mut bytes[filled..]) {
+                Ok(0) => return Ok(None),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        LogRecord::decode(&bytes)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Iterate over raw records instead of scaled samples
+    pub fn records(self) -> LogRecords<R> {
+        LogRecords { reader: self }
+    }
+
+    /// Destroy the reader and return the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R> Iterator for LogReader<R>
+where
+    R: Read,
+{
+    type Item = io::Result<ScaledSample>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_record() {
+            Ok(Some(record)) => Some(Ok(self.header.scale(&record))),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Iterator over the raw records of a log, see [`LogReader::records`]
+pub struct LogRecords<R> {
+    reader: LogReader<R>,
+}
+
+impl<R> LogRecords<R> {
+    /// Get log header
+    pub fn header(&self) -> &LogHeader {
+        &self.reader.header
+    }
+}
+
+impl<R> Iterator for LogRecords<R>
+where
+    R: Read,
+{
+    type Item = io::Result<LogRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.reader.next_record().transpose()
+    }
+}
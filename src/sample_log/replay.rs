@@ -0,0 +1,284 @@
+use std::fmt;
+use std::io::{self, BufRead};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::{AccelConfig, GyroConfig};
+use crate::mpu6050::{round_to_i16, ChipVariant, Sample, SampleSource, Temperature, Vector3};
+use crate::sample_log::format::LogRecord;
+use crate::sample_log::reader::LogRecords;
+
+/// How a [`Replay`] paces samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pacing {
+    /// Wait so samples are returned with their original spacing
+    Realtime,
+    /// Return samples as fast as they are requested
+    AsFastAsPossible,
+}
+
+/// Error returned by [`Replay`]
+#[derive(Debug)]
+pub enum ReplayError {
+    /// All samples have been replayed
+    EndOfLog,
+    /// Reading the recording failed
+    Io(io::Error),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EndOfLog => write!(f, "End of log"),
+            Self::Io(e) => write!(f, "Replay I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Plays back a recording through [`SampleSource`], the same interface as
+/// [`crate::mpu6050::Mpu6050`], so fusion and detection code can be regression-tested
+/// deterministically.
+///
+/// Samples are returned raw and uncalibrated, exactly as the driver would read them.
+pub struct Replay<S> {
+    records: S,
+    pacing: Pacing,
+    start: Option<(Instant, u64)>,
+    last_timestamp_us: Option<u64>,
+}
+
+impl<S> Replay<S>
+where
+    S: Iterator<Item = io::Result<LogRecord>>,
+{
+    /// Replay any source of timestamped records
+    pub fn new(records: S, pacing: Pacing) -> Self {
+        Self {
+            records,
+            pacing,
+            start: None,
+            last_timestamp_us: None,
+        }
+    }
+
+    /// Get the record source, e.g. to access the header of a binary log
+    pub fn source(&self) -> &S {
+        &self.records
+    }
+
+    /// Get timestamp of the sample returned last, in µs
+    pub fn last_timestamp_us(&self) -> Option<u64> {
+        self.last_timestamp_us
+    }
+
+    /// Read the next sample together with its recorded timestamp
+    pub fn read_record(&mut self) -> Result<LogRecord, ReplayError> {
+        let record = self.records.next().ok_or(ReplayError::EndOfLog)??;
+
+        if self.pacing == Pacing::Realtime {
+            let (start, first_us) = *self
+                .start
+                .get_or_insert((Instant::now(), record.timestamp_us));
+            let offset = Duration::from_micros(record.timestamp_us.saturating_sub(first_us));
+            if let Some(wait) = (start + offset).checked_duration_since(Instant::now()) {
+                thread::sleep(wait);
+            }
+        }
+        self.last_timestamp_us = Some(record.timestamp_us);
+        Ok(record)
+    }
+}
+
+impl<R> Replay<LogRecords<R>>
+where
+    R: io::Read,
+{
+    /// Replay a binary sample log
+    pub fn from_log(records: LogRecords<R>, pacing: Pacing) -> Self {
+        Self::new(records, pacing)
+    }
+}
+
+impl<R> Replay<CsvRecords<R>>
+where
+    R: BufRead,
+{
+    /// Replay CSV with columns `t_us,ax,ay,az,gx,gy,gz,temp` in µs, g, °/s and °C,
+    /// as written by `mpu6050-cli stream`
+    ///
+    /// Values are converted back to raw LSB using the given full scale ranges and the MPU-6050
+    /// temperature formula. Use [`CsvRecords::with_variant`] for recordings of other family
+    /// members.
+    pub fn from_csv(reader: R, gyro: GyroConfig, accel: AccelConfig, pacing: Pacing) -> Self {
+        Self::new(CsvRecords::new(reader, gyro, accel), pacing)
+    }
+}
+
+impl<S> SampleSource for Replay<S>
+where
+    S: Iterator<Item = io::Result<LogRecord>>,
+{
+    type Error = ReplayError;
+
+    fn read_sample(&mut self) -> Result<Sample, Self::Error> {
+        Ok(self.read_record()?.sample)
+    }
+}
+
+/// Iterator parsing scaled CSV samples back into raw records
+pub struct CsvRecords<R> {
+    lines: io::Lines<R>,
+    gyro_lsb: f32,
+    accel_lsb: f32,
+    variant: ChipVariant,
+}
+
+impl<R> CsvRecords<R>
+where
+    R: BufRead,
+{
+    pub fn new(reader: R, gyro: GyroConfig, accel: AccelConfig) -> Self {
+        Self {
+            lines: reader.lines(),
+            gyro_lsb: 32768.0 / gyro.scale_range() as f32,
+            accel_lsb: 32768.0 / accel.scale_range() as f32,
+            variant: ChipVariant::Mpu6050,
+        }
+    }
+
+    /// Convert temperatures with the formula of `variant` instead of the MPU-6050
+    pub fn with_variant(mut self, variant: ChipVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    fn parse(&self, line: &str) -> io::Result<LogRecord> {
+        let invalid = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                std::format!("invalid CSV line: {line}"),
+            )
+        };
+        let mut fields = line.split(',').map(str::trim);
+        let timestamp_us = fields
+            .next()
+            .and_then(|field| field.parse().ok())
+            .ok_or_else(invalid)?;
+        let mut values = [0.0f32; 7];
+        for value in values.iter_mut() {
+            *value = fields
+                .next()
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(invalid)?;
+        }
+        let raw = |value: f32, lsb: f32| round_to_i16(value * lsb);
+        Ok(LogRecord {
+            timestamp_us,
+            sample: Sample {
+                accel: Vector3 {
+                    x: raw(values[0], self.accel_lsb),
+                    y: raw(values[1], self.accel_lsb),
+                    z: raw(values[2], self.accel_lsb),
+                },
+                gyro: Vector3 {
+                    x: raw(values[3], self.gyro_lsb),
+                    y: raw(values[4], self.gyro_lsb),
+                    z: raw(values[5], self.gyro_lsb),
+                },
+                temp: Temperature::from_celsius_for(values[6], self.variant),
+            },
+        })
+    }
+}
+
+impl<R> Iterator for CsvRecords<R>
+where
+    R: BufRead,
+{
+    type Item = io::Result<LogRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            let line = line.trim();
+            // Skip blank lines and the header
+            if line.is_empty() || !line.starts_with(|c: char| c.is_ascii_digit()) {
+                continue;
+            }
+            return Some(self.parse(line));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "t_us,ax,ay,az,gx,gy,gz,temp\n\
+                       0,0.00000,0.50000,-1.00000,0.0000,125.0000,-250.0000,36.53\n\
+                       10000,1.00000,0.00000,0.00000,1.0000,0.0000,0.0000,21.00\n";
+
+    #[test]
+    fn csv_converts_back_to_raw() {
+        let mut records = CsvRecords::new(CSV.as_bytes(), GyroConfig::Dps250, AccelConfig::Range2G);
+        let record = records.next().unwrap().unwrap();
+        assert_eq!(record.timestamp_us, 0);
+        assert_eq!(
+            record.sample.accel,
+            Vector3 {
+                x: 0,
+                y: 8192,
+                z: -16384
+            }
+        );
+        assert_eq!(
+            record.sample.gyro,
+            Vector3 {
+                x: 0,
+                y: 16384,
+                z: -32768
+            }
+        );
+        assert_eq!(record.sample.temp.raw(), 0);
+        assert_eq!(records.next().unwrap().unwrap().timestamp_us, 10000);
+        assert!(records.next().is_none());
+    }
+
+    #[test]
+    fn csv_temperature_uses_variant() {
+        let records = CsvRecords::new(CSV.as_bytes(), GyroConfig::Dps250, AccelConfig::Range2G);
+        let temps: std::vec::Vec<_> = records
+            .with_variant(ChipVariant::Mpu6500)
+            .map(|record| record.unwrap().sample.temp)
+            .collect();
+        // 21 °C is the MPU-6500 room temperature offset
+        assert_eq!(temps[1].raw(), 0);
+        assert_eq!(
+            temps[0],
+            Temperature::from_celsius_for(36.53, ChipVariant::Mpu6500)
+        );
+        assert!((temps[0].celsius_f32_for(ChipVariant::Mpu6500) - 36.53).abs() < 0.01);
+    }
+
+    #[test]
+    fn csv_rejects_short_lines() {
+        let mut records = CsvRecords::new(
+            "0,1.0,2.0\n".as_bytes(),
+            GyroConfig::Dps250,
+            AccelConfig::Range2G,
+        );
+        let error = records.next().unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
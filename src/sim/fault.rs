@@ -0,0 +1,515 @@
+use embedded_hal::i2c::{ErrorKind, ErrorType, NoAcknowledgeSource, Operation, SevenBitAddress};
+
+/// Maximum number of rules a [`FaultyI2c`] holds at once
+pub const MAX_RULES: usize = 8;
+
+/// Number of bytes frozen by [`Fault::StaleData`]
+const STALE_LEN: usize = 32;
+
+/// Fault injected into a matching transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub enum Fault {
+    /// Device does not acknowledge, the transaction is not forwarded
+    Nak,
+    /// Arbitration lost, the transaction is not forwarded
+    ArbitrationLoss,
+    /// Bytes read are XORed with the mask
+    BitFlip(u8),
+    /// Every byte read has this value
+    StuckByte(u8),
+    /// Data read is frozen at the value returned the first time the rule matched
+    StaleData,
+    /// Bus drops out after this many bytes have been read: the rest of the buffer
+    /// reads 0xFF and a bus error is returned
+    Dropout(usize),
+}
+
+/// When and how often a [`Fault`] is injected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct FaultRule {
+    fault: Fault,
+    register: Option<u8>,
+    skip: u32,
+    remaining: Option<u32>,
+    stale: Option<([u8; STALE_LEN], usize)>,
+}
+
+impl FaultRule {
+    /// Create rule injecting `fault` into every transaction
+    pub const fn new(fault: Fault) -> Self {
+        Self {
+            fault,
+            register: None,
+            skip: 0,
+            remaining: None,
+            stale: None,
+        }
+    }
+
+    /// Only match transactions starting at this register address
+    pub const fn on_register(mut self, register: u8) -> Self {
+        self.register = Some(register);
+        self
+    }
+
+    /// Let the first `count` matching transactions pass untouched
+    pub const fn after(mut self, count: u32) -> Self {
+        self.skip = count;
+        self
+    }
+
+    /// Inject the fault `count` times, then retire the rule
+    ///
+    /// A count of 0 never injects the fault.
+    pub const fn times(mut self, count: u32) -> Self {
+        self.remaining = Some(count);
+        self
+    }
+
+    /// Get injected fault
+    pub const fn fault(&self) -> Fault {
+        self.fault
+    }
+
+    fn matches(&self, register: Option<u8>) -> bool {
+        self.register.is_none() || self.register == register
+    }
+}
+
+/// Error returned by [`FaultyI2c`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub enum FaultError<E> {
+    /// Error injected by a rule
+    Injected(ErrorKind),
+    /// Error from the wrapped bus
+    Bus(E),
+}
+
+impl<E> embedded_hal::i2c::Error for FaultError<E>
+where
+    E: embedded_hal::i2c::Error,
+{
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Injected(kind) => *kind,
+            Self::Bus(e) => e.kind(),
+        }
+    }
+}
+
+/// Wrapper around any [`embedded_hal::i2c::I2c`] that injects scripted faults
+///
+/// Rules are matched against the register address, i.e. the first byte written in the
+/// transaction. The first active matching rule is applied. Pair with
+/// [`super::SimulatedMpu6050`] to exercise error paths without hardware.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct FaultyI2c<I2C> {
+    i2c: I2C,
+    rules: [Option<FaultRule>; MAX_RULES],
+    injected: u32,
+}
+
+impl<I2C> FaultyI2c<I2C>
+where
+    I2C: embedded_hal::i2c::I2c,
+{
+    /// Wrap a bus, no faults are injected until rules are added
+    pub const fn new(i2c: I2C) -> Self {
+        Self {
+            i2c,
+            rules: [None; MAX_RULES],
+            injected: 0,
+        }
+    }
+
+    /// Add a rule
+    ///
+    /// Returns the rule back if all [`MAX_RULES`] slots are taken. A rule created with
+    /// [`FaultRule::times`]`(0)` is exhausted already and not stored.
+    pub fn inject(&mut self, rule: FaultRule) -> Result<(), FaultRule> {
+        if rule.remaining == Some(0) {
+            return Ok(());
+        }
+        match self.rules.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                *slot = Some(rule);
+                Ok(())
+            }
+            None => Err(rule),
+        }
+    }
+
+    /// Remove all rules
+    pub fn clear(&mut self) {
+        self.rules = [None; MAX_RULES];
+    }
+
+    /// Get number of faults injected so far
+    pub const fn injected(&self) -> u32 {
+        self.injected
+    }
+
+    /// Get mutable access to the wrapped bus
+    pub fn inner_mut(&mut self) -> &mut I2C {
+        &mut self.i2c
+    }
+
+    /// Destroy the wrapper and return the wrapped bus
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Run a transaction with the fault of rule `index` applied
+    fn apply_rule(
+        &mut self,
+        index: usize,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), FaultError<I2C::Error>> {
+        let Some(rule) = self.rules[index].as_mut() else {
+            return self.i2c.transaction(address, operations).map_err(FaultError::Bus);
+        };
+
+        match rule.fault {
+            Fault::Nak => {
+                return Err(FaultError::Injected(ErrorKind::NoAcknowledge(
+                    NoAcknowledgeSource::Data,
+                )))
+            }
+            Fault::ArbitrationLoss => return Err(FaultError::Injected(ErrorKind::ArbitrationLoss)),
+            _ => {}
+        }
+
+        self.i2c
+            .transaction(address, operations)
+            .map_err(FaultError::Bus)?;
+
+        let mut offset = 0;
+        let mut result = Ok(());
+        for operation in operations.iter_mut() {
+            let Operation::Read(buffer) = operation else {
+                continue;
+            };
+            for byte in buffer.iter_mut() {
+                match rule.fault {
+                    Fault::BitFlip(mask) => *byte ^= mask,
+                    Fault::StuckByte(value) => *byte = value,
+                    Fault::StaleData => match rule.stale.as_mut() {
+                        Some((snapshot, len)) if offset < *len => *byte = snapshot[offset],
+                        _ => {}
+                    },
+                    Fault::Dropout(after) if offset >= after => {
+                        *byte = 0xFF;
+                        result = Err(FaultError::Injected(ErrorKind::Bus));
+                    }
+                    _ => {}
+                }
+                offset += 1;
+            }
+        }
+
+        if rule.fault == Fault::StaleData && rule.stale.is_none() {
+            // First match: remember what was read, later matches return it
+            let mut snapshot = [0u8; STALE_LEN];
+            let mut len = 0;
+            for operation in operations.iter() {
+                if let Operation::Read(buffer) = operation {
+                    for &byte in buffer.iter() {
+                        if len < STALE_LEN {
+                            snapshot[len] = byte;
+                            len += 1;
+                        }
+                    }
+                }
+            }
+            rule.stale = Some((snapshot, len));
+        }
+        result
+    }
+
+    /// Find the rule to apply to a transaction and update its counters
+    fn select_rule(&mut self, register: Option<u8>) -> Option<usize> {
+        for (index, slot) in self.rules.iter_mut().enumerate() {
+            let Some(rule) = slot else {
+                continue;
+            };
+            if !rule.matches(register) {
+                continue;
+            }
+            if rule.skip > 0 {
+                rule.skip -= 1;
+                continue;
+            }
+            if let Some(remaining) = rule.remaining.as_mut() {
+                let Some(left) = remaining.checked_sub(1) else {
+                    // Exhausted, free the slot
+                    *slot = None;
+                    continue;
+                };
+                *remaining = left;
+            }
+            self.injected += 1;
+            return Some(index);
+        }
+        None
+    }
+}
+
+impl<I2C> ErrorType for FaultyI2c<I2C>
+where
+    I2C: embedded_hal::i2c::I2c,
+{
+    type Error = FaultError<I2C::Error>;
+}
+
+impl<I2C> embedded_hal::i2c::I2c for FaultyI2c<I2C>
+where
+    I2C: embedded_hal::i2c::I2c,
+{
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let register = match operations.first() {
+            Some(Operation::Write(bytes)) => bytes.first().copied(),
+            _ => None,
+        };
+        let Some(index) = self.select_rule(register) else {
+            return self.i2c.transaction(address, operations).map_err(FaultError::Bus);
+        };
+        let result = self.apply_rule(index, address, operations);
+        if self.rules[index].is_some_and(|rule| rule.remaining == Some(0)) {
+            self.rules[index] = None;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::i2c::{Error, ErrorKind, I2c, NoAcknowledgeSource};
+
+    use super::*;
+    use crate::config::{DeviceConfig, FifoConfig, GyroConfig, PwrMgmt1};
+    use crate::errors::MPU6050Error;
+    use crate::mpu6050::{Mpu6050, Vector3};
+    use crate::registers::Registers;
+    use crate::sim::{SimError, SimulatedMpu6050};
+
+    type Driver = Mpu6050<FaultyI2c<SimulatedMpu6050>>;
+
+    fn driver(rule: FaultRule) -> Driver {
+        let mut sim = SimulatedMpu6050::default();
+        sim.poke(Registers::PowerMgmt1.get_register_address(), 0x01);
+        let mut i2c = FaultyI2c::new(sim);
+        i2c.inject(rule).unwrap();
+        Mpu6050::new(i2c, 0x68)
+    }
+
+    fn assert_register_access(
+        result: Result<impl core::fmt::Debug, MPU6050Error<FaultError<SimError>>>,
+        register: Registers,
+        kind: ErrorKind,
+    ) {
+        match result {
+            Err(MPU6050Error::RegisterAccess { register: r, error }) => {
+                assert_eq!(r, register.get_register_address());
+                assert_eq!(error.kind(), kind);
+            }
+            other => panic!("expected RegisterAccess, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn read_reports_register_and_kind() {
+        let accel = Registers::AccelXOutH.get_register_address();
+        let mut mpu = driver(FaultRule::new(Fault::Nak).on_register(accel));
+        assert_register_access(
+            mpu.read_sample(),
+            Registers::AccelXOutH,
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+        );
+        // Other registers are not affected
+        assert!(mpu.read_register(Registers::WhoAmI).is_ok());
+    }
+
+    #[test]
+    fn configure_reports_register_and_kind() {
+        let gyro_config = Registers::GyroConfig.get_register_address();
+        let mut mpu = driver(FaultRule::new(Fault::ArbitrationLoss).on_register(gyro_config));
+        assert_register_access(
+            mpu.configure_gyro(GyroConfig::Dps500),
+            Registers::GyroConfig,
+            ErrorKind::ArbitrationLoss,
+        );
+    }
+
+    #[test]
+    fn fifo_read_reports_register_and_kind() {
+        let fifo_rw = Registers::FifoRW.get_register_address();
+        let mut mpu = driver(FaultRule::new(Fault::Dropout(2)).on_register(fifo_rw));
+        mpu.configure_fifo(FifoConfig::new().enable_accel(true))
+            .unwrap();
+        mpu.set_fifo_enabled(true).unwrap();
+        assert_register_access(mpu.read_fifo_frame(), Registers::FifoRW, ErrorKind::Bus);
+    }
+
+    #[test]
+    fn fifo_count_reports_register_and_kind() {
+        let fifo_count = Registers::FifoCountH.get_register_address();
+        let mut mpu = driver(FaultRule::new(Fault::Nak).on_register(fifo_count).times(1));
+        assert_register_access(
+            mpu.get_fifo_count(),
+            Registers::FifoCountH,
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+        );
+        assert_eq!(mpu.get_fifo_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn apply_config_reports_register_and_kind() {
+        let accel_config = Registers::AccelConfig.get_register_address();
+        let mut mpu = driver(FaultRule::new(Fault::Nak).on_register(accel_config));
+        let config = DeviceConfig::new().with_pwr_mgmt(PwrMgmt1::new());
+        assert_register_access(
+            mpu.apply_config(&config),
+            Registers::AccelConfig,
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+        );
+    }
+
+    #[test]
+    fn apply_config_reports_corrupted_read_back() {
+        let gyro_config = Registers::GyroConfig.get_register_address();
+        // The write passes, the read back is corrupted
+        let rule = FaultRule::new(Fault::BitFlip(0x01))
+            .on_register(gyro_config)
+            .after(1)
+            .times(1);
+        let mut mpu = driver(rule);
+        let config = DeviceConfig::new().with_pwr_mgmt(PwrMgmt1::new());
+        match mpu.apply_config(&config) {
+            Err(MPU6050Error::VerificationMismatch {
+                register,
+                expected,
+                actual,
+            }) => {
+                assert_eq!(register, gyro_config);
+                assert_eq!(actual, expected ^ 0x01);
+            }
+            other => panic!("expected VerificationMismatch, got {other:?}"),
+        }
+        // The rule is retired, applying again succeeds
+        mpu.apply_config(&config).unwrap();
+    }
+
+    #[test]
+    fn times_zero_never_injects() {
+        let mut mpu = driver(FaultRule::new(Fault::Nak).times(0));
+        assert!(mpu.read_sample().is_ok());
+        assert!(mpu.read_sample().is_ok());
+    }
+
+    #[test]
+    fn exhausted_rules_free_their_slot() {
+        let mut i2c = FaultyI2c::new(SimulatedMpu6050::default());
+        for _ in 0..MAX_RULES {
+            i2c.inject(FaultRule::new(Fault::Nak).times(0)).unwrap();
+        }
+        i2c.inject(FaultRule::new(Fault::Nak).times(1)).unwrap();
+        for _ in 1..MAX_RULES {
+            i2c.inject(FaultRule::new(Fault::Nak).on_register(0x00))
+                .unwrap();
+        }
+        let rule = FaultRule::new(Fault::ArbitrationLoss);
+        assert_eq!(i2c.inject(rule), Err(rule));
+
+        // The first transaction uses up the times(1) rule, which frees its slot
+        let mut mpu = Mpu6050::new(i2c, 0x68);
+        assert!(mpu.read_sample().is_err());
+        assert!(mpu.read_sample().is_ok());
+        let mut i2c = mpu.release();
+        assert_eq!(i2c.injected(), 1);
+        assert_eq!(i2c.inject(rule), Ok(()));
+    }
+
+    #[test]
+    fn after_skips_matching_transactions() {
+        let who_am_i = Registers::WhoAmI.get_register_address();
+        let mut mpu = driver(
+            FaultRule::new(Fault::Nak)
+                .on_register(who_am_i)
+                .after(2)
+                .times(1),
+        );
+        assert!(mpu.who_am_i().is_ok());
+        assert!(mpu.who_am_i().is_ok());
+        assert!(mpu.who_am_i().is_err());
+        assert!(mpu.who_am_i().is_ok());
+    }
+
+    #[test]
+    fn stuck_byte_replaces_data() {
+        let who_am_i = Registers::WhoAmI.get_register_address();
+        let mut mpu = driver(FaultRule::new(Fault::StuckByte(0xFF)).on_register(who_am_i));
+        match mpu.verify_device_id() {
+            Err(MPU6050Error::WrongDeviceId {
+                register,
+                expected,
+                actual,
+            }) => {
+                assert_eq!(register, who_am_i);
+                assert_eq!(expected, 0x68);
+                assert_eq!(actual, 0xFF);
+            }
+            other => panic!("expected WrongDeviceId, got {other:?}"),
+        }
+        // Only reads are affected, the write of the register address passes through
+        assert_eq!(mpu.read_accel().unwrap(), Vector3 { x: 0, y: 0, z: 0 });
+    }
+
+    #[test]
+    fn stale_data_freezes_reads() {
+        let accel = Registers::AccelXOutH.get_register_address();
+        let mut i2c = FaultyI2c::new(SimulatedMpu6050::default());
+        i2c.inner_mut()
+            .poke(Registers::PowerMgmt1.get_register_address(), 0x01);
+        i2c.inject(FaultRule::new(Fault::StaleData).on_register(accel))
+            .unwrap();
+        let read_accel = |i2c: &mut FaultyI2c<SimulatedMpu6050>, x: i16| {
+            i2c.inner_mut().set_accel_raw(x, 0, 0);
+            i2c.inner_mut().step(1);
+            let mut buffer = [0u8; 2];
+            i2c.write_read(0x68, &[accel], &mut buffer).unwrap();
+            i16::from_be_bytes(buffer)
+        };
+
+        assert_eq!(read_accel(&mut i2c, 1), 1);
+        assert_eq!(read_accel(&mut i2c, 2), 1);
+        assert_eq!(read_accel(&mut i2c, 3), 1);
+        i2c.clear();
+        assert_eq!(read_accel(&mut i2c, 4), 4);
+    }
+
+    #[test]
+    fn dropout_fills_rest_of_read() {
+        let accel = Registers::AccelXOutH.get_register_address();
+        let mut i2c = FaultyI2c::new(SimulatedMpu6050::default());
+        i2c.inner_mut()
+            .poke(Registers::PowerMgmt1.get_register_address(), 0x01);
+        i2c.inner_mut().set_accel_raw(0x0102, 0x0304, 0x0506);
+        i2c.inner_mut().step(1);
+        i2c.inject(FaultRule::new(Fault::Dropout(3)).on_register(accel))
+            .unwrap();
+
+        let mut buffer = [0u8; 6];
+        let result = i2c.write_read(0x68, &[accel], &mut buffer);
+        assert_eq!(result, Err(FaultError::Injected(ErrorKind::Bus)));
+        assert_eq!(buffer, [1, 2, 3, 0xFF, 0xFF, 0xFF]);
+    }
+}
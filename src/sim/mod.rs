@@ -0,0 +1,5 @@
+
+pub mod fault;
+pub mod simulator;
+pub use fault::{Fault, FaultError, FaultRule, FaultyI2c};
+pub use simulator::{SimError, SimulatedMpu6050};
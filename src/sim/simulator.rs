@@ -0,0 +1,703 @@
+use embedded_hal::i2c::{ErrorKind, ErrorType, NoAcknowledgeSource, Operation, SevenBitAddress};
+
+use crate::config::{AccelConfig, ExtSync, GyroConfig};
+use crate::mpu6050::self_test::{accel_factory_trim, decode_test_values, gyro_factory_trim};
+use crate::mpu6050::{round_to_i16, ChipVariant, Temperature};
+use crate::registers::Registers;
+
+/// WHO_AM_I value reported by the MPU-6050
+pub const WHO_AM_I_VALUE: u8 = ChipVariant::Mpu6050.who_am_i();
+
+/// Factory self-test value programmed into SELF_TEST_X, SELF_TEST_Y and SELF_TEST_Z:
+/// XA_TEST[4:2] = 0b100 and XG_TEST = 16
+const SELF_TEST_XYZ_VALUE: u8 = 0x90;
+
+/// FIFO size in bytes
+pub const FIFO_SIZE: usize = ChipVariant::Mpu6050.fifo_size() as usize;
+
+const REGISTER_COUNT: usize = 128;
+
+// Bits used by the simulator
+const PWR_DEVICE_RESET: u8 = 1 << 7;
+const PWR_SLEEP: u8 = 1 << 6;
+const PWR_CYCLE: u8 = 1 << 5;
+const PWR_CLOCK_STOP: u8 = 7;
+const USER_FIFO_EN: u8 = 1 << 6;
+const USER_FIFO_RESET: u8 = 1 << 2;
+const USER_I2C_MST_RESET: u8 = 1 << 1;
+const USER_SIG_COND_RESET: u8 = 1 << 0;
+const INT_FIFO_OFLOW: u8 = 1 << 4;
+const INT_DATA_RDY: u8 = 1 << 0;
+const INT_PIN_LEVEL: u8 = 1 << 7;
+const INT_PIN_RD_CLEAR: u8 = 1 << 4;
+const INT_PIN_FSYNC_LEVEL: u8 = 1 << 3;
+const INT_PIN_FSYNC_INT_EN: u8 = 1 << 2;
+const I2C_MST_PASS_THROUGH: u8 = 1 << 7;
+
+/// Error returned by the simulated bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub enum SimError {
+    /// Transaction was addressed to a different device
+    AddressNak(SevenBitAddress),
+}
+
+impl embedded_hal::i2c::Error for SimError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::AddressNak(_) => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+        }
+    }
+}
+
+/// Register-accurate software model of an MPU-6050
+///
+/// Implements [`embedded_hal::i2c::I2c`] so it can be handed to [`crate::mpu6050::Mpu6050`]
+/// in place of a real bus. Time does not pass on its own: call
+/// [`SimulatedMpu6050::advance_us`] or [`SimulatedMpu6050::step`] to produce samples at the
+/// configured sample rate, which updates the data registers, fills the FIFO and sets the
+/// interrupt status bits.
+///
+/// Modelled behaviour:
+/// - reset values (WHO_AM_I = 0x68, PWR_MGMT_1 = 0x40 i.e. sleeping) and DEVICE_RESET
+/// - auto-incrementing burst reads and writes, except FIFO_R_W which pops the FIFO
+/// - FIFO_EN, FIFO_RESET and SIG_COND_RESET in USER_CTRL
+/// - FIFO ordering per FIFO_EN, 1024 byte FIFO where overflow discards the oldest data
+/// - INT_STATUS DATA_RDY and FIFO_OFLOW bits, cleared on read (or on any read with INT_RD_CLEAR)
+/// - sample rate from DLPF_CFG and SMPLRT_DIV, Cycle mode at the LP_WAKE_CTRL rate
+/// - FSYNC: edges on the pin are latched into the data LSB selected by EXT_SYNC_SET and,
+///   with FSYNC_INT_EN, into PASS_THROUGH of I2C_MST_STATUS (cleared on read)
+/// - self-test: the XA_ST..ZG_ST bits add the factory trim response, so a healthy
+///   device passes [`crate::mpu6050::Mpu6050::self_test`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct SimulatedMpu6050 {
+    address: SevenBitAddress,
+    registers: [u8; REGISTER_COUNT],
+    pointer: u8,
+    fifo: [u8; FIFO_SIZE],
+    fifo_head: usize,
+    fifo_len: usize,
+    accel: [i16; 3],
+    gyro: [i16; 3],
+    temp: i16,
+    fsync_pin: bool,
+    fsync_latched: bool,
+    /// Elapsed time since the last sample, in µs·Hz
+    phase: u64,
+    samples: u64,
+}
+
+impl SimulatedMpu6050 {
+    /// Create simulated device responding at `address` (0x68 or 0x69 on real hardware)
+    pub fn new(address: SevenBitAddress) -> Self {
+        let mut sim = Self {
+            address,
+            registers: [0; REGISTER_COUNT],
+            pointer: 0,
+            fifo: [0; FIFO_SIZE],
+            fifo_head: 0,
+            fifo_len: 0,
+            accel: [0; 3],
+            gyro: [0; 3],
+            temp: 0,
+            fsync_pin: false,
+            fsync_latched: false,
+            phase: 0,
+            samples: 0,
+        };
+        sim.reset();
+        sim
+    }
+
+    /// Reset all registers to their power-on values. Injected sensor values are kept.
+    pub fn reset(&mut self) {
+        self.registers = [0; REGISTER_COUNT];
+        self.registers[Registers::PowerMgmt1.get_register_address() as usize] = PWR_SLEEP;
+        self.registers[Registers::WhoAmI.get_register_address() as usize] = WHO_AM_I_VALUE;
+        for register in [Registers::SelfTestX, Registers::SelfTestY, Registers::SelfTestZ] {
+            self.registers[register.get_register_address() as usize] = SELF_TEST_XYZ_VALUE;
+        }
+        self.pointer = 0;
+        self.fsync_latched = false;
+        self.clear_fifo();
+        self.phase = 0;
+    }
+
+    /// Get I2C address the device responds to
+    pub const fn address(&self) -> SevenBitAddress {
+        self.address
+    }
+
+    /// Set raw accelerometer output for X, Y and Z
+    pub fn set_accel_raw(&mut self, x: i16, y: i16, z: i16) {
+        self.accel = [x, y, z];
+    }
+
+    /// Set raw gyroscope output for X, Y and Z
+    pub fn set_gyro_raw(&mut self, x: i16, y: i16, z: i16) {
+        self.gyro = [x, y, z];
+    }
+
+    /// Set raw TEMP_OUT value
+    pub fn set_temp_raw(&mut self, raw: i16) {
+        self.temp = raw;
+    }
+
+    /// Set accelerometer output in g, scaled with the configured full scale range
+    pub fn set_accel_g(&mut self, x: f32, y: f32, z: f32) {
+        let range = AccelConfig::from_register(self.register(Registers::AccelConfig));
+        let lsb_per_g = 32768.0 / range.scale_range() as f32;
+        self.accel = [x, y, z].map(|value| round_to_i16(value * lsb_per_g));
+    }
+
+    /// Set gyroscope output in °/s, scaled with the configured full scale range
+    pub fn set_gyro_dps(&mut self, x: f32, y: f32, z: f32) {
+        let range = GyroConfig::from_register(self.register(Registers::GyroConfig));
+        let lsb_per_dps = 32768.0 / range.scale_range() as f32;
+        self.gyro = [x, y, z].map(|value| round_to_i16(value * lsb_per_dps));
+    }
+
+    /// Set die temperature in °C
+    pub fn set_temperature_celsius(&mut self, celsius: f32) {
+        self.temp = Temperature::from_celsius(celsius).raw();
+    }
+
+    /// Drive the FSYNC pin
+    ///
+    /// A transition to the active level (per FSYNC_INT_LEVEL) is latched until the next sample.
+    pub fn set_fsync(&mut self, high: bool) {
+        let active_low = self.register(Registers::InterruptPinCfg) & INT_PIN_FSYNC_LEVEL != 0;
+        let was_active = self.fsync_pin != active_low;
+        self.fsync_pin = high;
+        if high != active_low && !was_active {
+            self.fsync_latched = true;
+            if self.register(Registers::InterruptPinCfg) & INT_PIN_FSYNC_INT_EN != 0 {
+                *self.register_mut(Registers::I2cMstStatus) |= I2C_MST_PASS_THROUGH;
+            }
+        }
+    }
+
+    /// Read a register without side effects
+    pub fn peek(&self, address: u8) -> u8 {
+        match address {
+            0x72 => (self.fifo_len >> 8) as u8,
+            0x73 => self.fifo_len as u8,
+            _ => self.registers[address as usize & (REGISTER_COUNT - 1)],
+        }
+    }
+
+    /// Write a register directly, bypassing the bus and any side effects
+    pub fn poke(&mut self, address: u8, value: u8) {
+        self.registers[address as usize & (REGISTER_COUNT - 1)] = value;
+    }
+
+    /// Get number of bytes in the FIFO
+    pub const fn fifo_len(&self) -> usize {
+        self.fifo_len
+    }
+
+    /// Get number of samples produced since creation
+    pub const fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Check whether the device is sampling (not sleeping and clock running)
+    pub fn is_awake(&self) -> bool {
+        let pwr = self.register(Registers::PowerMgmt1);
+        pwr & PWR_SLEEP == 0 && pwr & 0x07 != PWR_CLOCK_STOP
+    }
+
+    /// Current sample rate in Hz
+    ///
+    /// In Cycle mode this is the LP_WAKE_CTRL frequency rounded down, 1.25 Hz is reported as 1.
+    pub fn sample_rate_hz(&self) -> u32 {
+        if self.register(Registers::PowerMgmt1) & PWR_CYCLE != 0 {
+            return match self.register(Registers::PowerMgmt2) >> 6 {
+                0 => 1,
+                1 => 5,
+                2 => 20,
+                _ => 40,
+            };
+        }
+        let gyro_rate = match self.register(Registers::Config) & 0x07 {
+            0 | 7 => 8000,
+            _ => 1000,
+        };
+        gyro_rate / (1 + self.register(Registers::SmprtDiv) as u32)
+    }
+
+    /// Check whether the INT pin is asserted, taking INT_ENABLE, the FSYNC interrupt and INT_LEVEL
+    /// into account
+    pub fn interrupt_pin_high(&self) -> bool {
+        let pending = self.register(Registers::InterruptStatus)
+            & self.register(Registers::InterruptEnable)
+            != 0;
+        let fsync_pending = self.register(Registers::I2cMstStatus) & I2C_MST_PASS_THROUGH != 0;
+        let pending = pending || fsync_pending;
+        let active_low = self.register(Registers::InterruptPinCfg) & INT_PIN_LEVEL != 0;
+        pending != active_low
+    }
+
+    /// Let `micros` microseconds pass, producing samples at the configured rate
+    ///
+    /// Returns the number of samples produced.
+    pub fn advance_us(&mut self, micros: u32) -> u32 {
+        if !self.is_awake() {
+            return 0;
+        }
+        let rate = self.sample_rate_hz() as u64;
+        self.phase += micros as u64 * rate;
+        let mut produced = 0;
+        while self.phase >= 1_000_000 {
+            self.phase -= 1_000_000;
+            self.sample();
+            produced += 1;
+        }
+        produced
+    }
+
+    /// Produce `count` samples immediately, regardless of the sample rate
+    pub fn step(&mut self, count: u32) {
+        if !self.is_awake() {
+            return;
+        }
+        for _ in 0..count {
+            self.sample();
+        }
+    }
+
+    fn register(&self, register: Registers) -> u8 {
+        self.registers[register.get_register_address() as usize]
+    }
+
+    fn register_mut(&mut self, register: Registers) -> &mut u8 {
+        &mut self.registers[register.get_register_address() as usize]
+    }
+
+    /// Latch sensor values into the output registers and the FIFO
+    fn sample(&mut self) {
+        let cycle = self.register(Registers::PowerMgmt1) & PWR_CYCLE != 0;
+        let temp_disabled = self.register(Registers::PowerMgmt1) & (1 << 3) != 0;
+        let standby = self.register(Registers::PowerMgmt2);
+
+        let (accel_st, gyro_st) = self.self_test_response();
+        let accel = [0, 1, 2].map(|axis| self.accel[axis].saturating_add(accel_st[axis]));
+        let gyro = [0, 1, 2].map(|axis| self.gyro[axis].saturating_add(gyro_st[axis]));
+
+        let mut output = [0u8; 14];
+        for (axis, value) in accel.iter().enumerate() {
+            if standby & (1 << (5 - axis)) == 0 {
+                output[axis * 2..axis * 2 + 2].copy_from_slice(&value.to_be_bytes());
+            }
+        }
+        if !temp_disabled {
+            output[6..8].copy_from_slice(&self.temp.to_be_bytes());
+        }
+        if !cycle {
+            for (axis, value) in gyro.iter().enumerate() {
+                if standby & (1 << (2 - axis)) == 0 {
+                    output[8 + axis * 2..10 + axis * 2].copy_from_slice(&value.to_be_bytes());
+                }
+            }
+        }
+        let ext_sync = ExtSync::from_register(self.register(Registers::Config));
+        if let Some(index) = ext_sync.sample_byte_index() {
+            output[index] = (output[index] & !0x01) | self.fsync_latched as u8;
+            self.fsync_latched = false;
+        }
+        let base = Registers::AccelXOutH.get_register_address() as usize;
+        self.registers[base..base + output.len()].copy_from_slice(&output);
+
+        if self.register(Registers::UserCtrl) & USER_FIFO_EN != 0 {
+            let fifo_en = self.register(Registers::FifoEn);
+            // Written in order of ascending register address
+            if fifo_en & (1 << 3) != 0 {
+                self.push_fifo(&output[0..6]);
+            }
+            if fifo_en & (1 << 7) != 0 {
+                self.push_fifo(&output[6..8]);
+            }
+            for axis in 0..3 {
+                if fifo_en & (1 << (6 - axis)) != 0 {
+                    self.push_fifo(&output[8 + axis * 2..10 + axis * 2]);
+                }
+            }
+        }
+
+        *self.register_mut(Registers::InterruptStatus) |= INT_DATA_RDY;
+        self.samples += 1;
+    }
+
+    /// Output change caused by the self-test bits in ACCEL_CONFIG and GYRO_CONFIG
+    fn self_test_response(&self) -> ([i16; 3], [i16; 3]) {
+        let mut registers = [0u8; 4];
+        let base = Registers::SelfTestX.get_register_address() as usize;
+        registers.copy_from_slice(&self.registers[base..base + 4]);
+        let (accel_test, gyro_test) = decode_test_values(registers);
+
+        let accel_config = self.register(Registers::AccelConfig);
+        let gyro_config = self.register(Registers::GyroConfig);
+        let accel_scale = 8.0 / AccelConfig::from_register(accel_config).scale_range() as f32;
+        let gyro_scale = 250.0 / GyroConfig::from_register(gyro_config).scale_range() as f32;
+
+        let mut accel = [0i16; 3];
+        let mut gyro = [0i16; 3];
+        for axis in 0..3 {
+            if accel_config & (1 << (7 - axis)) != 0 {
+                accel[axis] = round_to_i16(accel_factory_trim(accel_test[axis]) * accel_scale);
+            }
+            if gyro_config & (1 << (7 - axis)) != 0 {
+                let trim = round_to_i16(gyro_factory_trim(gyro_test[axis]) * gyro_scale);
+                gyro[axis] = if axis == 1 { -trim } else { trim };
+            }
+        }
+        (accel, gyro)
+    }
+
+    fn push_fifo(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            if self.fifo_len == FIFO_SIZE {
+                // Overflow: the oldest byte is overwritten
+                self.fifo_head = (self.fifo_head + 1) % FIFO_SIZE;
+                self.fifo_len -= 1;
+                *self.register_mut(Registers::InterruptStatus) |= INT_FIFO_OFLOW;
+            }
+            self.fifo[(self.fifo_head + self.fifo_len) % FIFO_SIZE] = byte;
+            self.fifo_len += 1;
+        }
+    }
+
+    fn pop_fifo(&mut self) -> u8 {
+        if self.fifo_len == 0 {
+            return 0;
+        }
+        let byte = self.fifo[self.fifo_head];
+        self.fifo_head = (self.fifo_head + 1) % FIFO_SIZE;
+        self.fifo_len -= 1;
+        byte
+    }
+
+    fn clear_fifo(&mut self) {
+        self.fifo_head = 0;
+        self.fifo_len = 0;
+    }
+
+    fn is_read_only(address: u8) -> bool {
+        matches!(address, 0x3A..=0x60 | 0x72 | 0x73 | 0x75)
+    }
+
+    fn advance_pointer(&mut self) {
+        // Burst access to FIFO_R_W keeps addressing the FIFO
+        if self.pointer != Registers::FifoRW.get_register_address() {
+            self.pointer = self.pointer.wrapping_add(1) & (REGISTER_COUNT as u8 - 1);
+        }
+    }
+
+    fn write_byte(&mut self, address: u8, value: u8) {
+        let pwr_mgmt_1 = Registers::PowerMgmt1.get_register_address();
+        let user_ctrl = Registers::UserCtrl.get_register_address();
+        let fifo_rw = Registers::FifoRW.get_register_address();
+
+        if address == fifo_rw {
+            self.push_fifo(&[value]);
+        } else if address == pwr_mgmt_1 && value & PWR_DEVICE_RESET != 0 {
+            self.reset();
+        } else if address == user_ctrl {
+            // FIFO_RESET is ignored while FIFO_EN stays set
+            if value & USER_FIFO_RESET != 0 && value & USER_FIFO_EN == 0 {
+                self.clear_fifo();
+            }
+            if value & USER_SIG_COND_RESET != 0 {
+                let base = Registers::AccelXOutH.get_register_address() as usize;
+                self.registers[base..base + 14].fill(0);
+            }
+            // Reset bits clear themselves
+            *self.register_mut(Registers::UserCtrl) =
+                value & !(USER_FIFO_RESET | USER_I2C_MST_RESET | USER_SIG_COND_RESET);
+        } else if !Self::is_read_only(address) {
+            self.registers[address as usize & (REGISTER_COUNT - 1)] = value;
+        }
+    }
+
+    fn read_byte(&mut self, address: u8) -> u8 {
+        let int_status = Registers::InterruptStatus.get_register_address();
+        let fifo_rw = Registers::FifoRW.get_register_address();
+
+        let value = if address == fifo_rw {
+            self.pop_fifo()
+        } else {
+            self.peek(address)
+        };
+        let clear_on_any_read = self.register(Registers::InterruptPinCfg) & INT_PIN_RD_CLEAR != 0;
+        if address == int_status || clear_on_any_read {
+            *self.register_mut(Registers::InterruptStatus) = 0;
+        }
+        if address == Registers::I2cMstStatus.get_register_address() {
+            *self.register_mut(Registers::I2cMstStatus) &= !I2C_MST_PASS_THROUGH;
+        }
+        value
+    }
+}
+
+impl Default for SimulatedMpu6050 {
+    fn default() -> Self {
+        Self::new(0x68)
+    }
+}
+
+impl ErrorType for SimulatedMpu6050 {
+    type Error = SimError;
+}
+
+impl embedded_hal::i2c::I2c for SimulatedMpu6050 {
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if address != self.address {
+            return Err(SimError::AddressNak(address));
+        }
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    let Some((&pointer, data)) = bytes.split_first() else {
+                        continue;
+                    };
+                    self.pointer = pointer;
+                    for &byte in data {
+                        self.write_byte(self.pointer, byte);
+                        self.advance_pointer();
+                    }
+                }
+                Operation::Read(buffer) => {
+                    for byte in buffer.iter_mut() {
+                        *byte = self.read_byte(self.pointer);
+                        self.advance_pointer();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::i2c::I2c;
+
+    use super::*;
+    use crate::mpu6050::Mpu6050;
+
+    const ADDRESS: SevenBitAddress = 0x68;
+
+    fn awake_sim() -> SimulatedMpu6050 {
+        let mut sim = SimulatedMpu6050::default();
+        sim.write(
+            ADDRESS,
+            &[Registers::PowerMgmt1.get_register_address(), 0x01],
+        )
+        .unwrap();
+        sim
+    }
+
+    fn enable_fifo(sim: &mut SimulatedMpu6050, fifo_en: u8) {
+        sim.write(
+            ADDRESS,
+            &[Registers::FifoEn.get_register_address(), fifo_en],
+        )
+        .unwrap();
+        sim.write(
+            ADDRESS,
+            &[Registers::UserCtrl.get_register_address(), USER_FIFO_EN],
+        )
+        .unwrap();
+    }
+
+    fn read(sim: &mut SimulatedMpu6050, register: Registers, buffer: &mut [u8]) {
+        sim.write_read(ADDRESS, &[register.get_register_address()], buffer)
+            .unwrap();
+    }
+
+    #[test]
+    fn fifo_fills_in_register_order() {
+        let mut sim = awake_sim();
+        sim.set_accel_raw(0x0102, 0x0304, 0x0506);
+        sim.set_temp_raw(0x0708);
+        sim.set_gyro_raw(0x090A, 0x0B0C, 0x0D0E);
+        // TEMP, XG, YG, ZG and ACCEL
+        enable_fifo(&mut sim, 0xF8);
+
+        sim.step(2);
+        assert_eq!(sim.fifo_len(), 28);
+        assert_eq!(sim.peek(0x72), 0);
+        assert_eq!(sim.peek(0x73), 28);
+
+        let mut frame = [0u8; 14];
+        read(&mut sim, Registers::FifoRW, &mut frame);
+        assert_eq!(frame, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
+        assert_eq!(sim.fifo_len(), 14);
+    }
+
+    #[test]
+    fn fifo_only_fills_while_enabled_and_awake() {
+        let mut sim = SimulatedMpu6050::default();
+        enable_fifo(&mut sim, 0x08);
+        sim.step(1);
+        assert_eq!(sim.fifo_len(), 0);
+
+        let mut sim = awake_sim();
+        sim.write(ADDRESS, &[Registers::FifoEn.get_register_address(), 0x08])
+            .unwrap();
+        sim.step(1);
+        assert_eq!(sim.fifo_len(), 0);
+    }
+
+    #[test]
+    fn fifo_overflow_discards_oldest_bytes() {
+        let mut sim = awake_sim();
+        // ACCEL only, 6 byte frames
+        enable_fifo(&mut sim, 0x08);
+        sim.set_accel_raw(1, 1, 1);
+        sim.step((FIFO_SIZE / 6) as u32);
+        assert_eq!(sim.fifo_len(), FIFO_SIZE / 6 * 6);
+        assert_eq!(sim.peek(0x3A) & INT_FIFO_OFLOW, 0);
+
+        sim.set_accel_raw(2, 2, 2);
+        sim.step(1);
+        assert_eq!(sim.fifo_len(), FIFO_SIZE);
+        assert_ne!(sim.peek(0x3A) & INT_FIFO_OFLOW, 0);
+
+        // 1026 bytes were written, so the FIFO starts two bytes into the first frame
+        let mut head = [0u8; 4];
+        read(&mut sim, Registers::FifoRW, &mut head);
+        assert_eq!(head, [0, 1, 0, 1]);
+        let mut rest = [0u8; FIFO_SIZE - 4];
+        read(&mut sim, Registers::FifoRW, &mut rest);
+        assert_eq!(rest[rest.len() - 6..], [0, 2, 0, 2, 0, 2]);
+        assert_eq!(sim.fifo_len(), 0);
+    }
+
+    #[test]
+    fn fifo_reset_needs_fifo_disabled() {
+        let mut sim = awake_sim();
+        enable_fifo(&mut sim, 0x08);
+        sim.step(3);
+        let user_ctrl = Registers::UserCtrl.get_register_address();
+        sim.write(ADDRESS, &[user_ctrl, USER_FIFO_EN | USER_FIFO_RESET])
+            .unwrap();
+        assert_eq!(sim.fifo_len(), 18);
+        assert_eq!(sim.peek(user_ctrl), USER_FIFO_EN);
+
+        sim.write(ADDRESS, &[user_ctrl, USER_FIFO_RESET]).unwrap();
+        assert_eq!(sim.fifo_len(), 0);
+        assert_eq!(sim.peek(user_ctrl), 0);
+    }
+
+    #[test]
+    fn driver_fifo_reset_keeps_fifo_enabled() {
+        let mut sim = awake_sim();
+        enable_fifo(&mut sim, 0x08);
+        sim.step(3);
+        let mut mpu = Mpu6050::new(sim, ADDRESS);
+        mpu.reset_fifo().unwrap();
+        let mut sim = mpu.release();
+        assert_eq!(sim.fifo_len(), 0);
+        assert_eq!(
+            sim.peek(Registers::UserCtrl.get_register_address()),
+            USER_FIFO_EN
+        );
+        sim.step(1);
+        assert_eq!(sim.fifo_len(), 6);
+    }
+
+    #[test]
+    fn int_status_clears_on_read() {
+        let mut sim = awake_sim();
+        sim.step(1);
+        let mut status = [0u8];
+        read(&mut sim, Registers::InterruptStatus, &mut status);
+        assert_eq!(status[0] & INT_DATA_RDY, INT_DATA_RDY);
+        read(&mut sim, Registers::InterruptStatus, &mut status);
+        assert_eq!(status[0], 0);
+
+        // Reading any other register leaves the status untouched
+        sim.step(1);
+        read(&mut sim, Registers::WhoAmI, &mut status);
+        assert_eq!(sim.peek(0x3A), INT_DATA_RDY);
+    }
+
+    #[test]
+    fn int_status_clears_on_any_read_with_int_rd_clear() {
+        let mut sim = awake_sim();
+        sim.write(
+            ADDRESS,
+            &[
+                Registers::InterruptPinCfg.get_register_address(),
+                INT_PIN_RD_CLEAR,
+            ],
+        )
+        .unwrap();
+        sim.step(1);
+        let mut who_am_i = [0u8];
+        read(&mut sim, Registers::WhoAmI, &mut who_am_i);
+        assert_eq!(who_am_i[0], WHO_AM_I_VALUE);
+        assert_eq!(sim.peek(0x3A), 0);
+    }
+
+    #[test]
+    fn burst_read_auto_increments() {
+        let mut sim = awake_sim();
+        sim.set_accel_raw(-2, 300, 16384);
+        sim.set_temp_raw(-521);
+        sim.set_gyro_raw(1, -1, 32767);
+        sim.step(1);
+
+        let mut buffer = [0u8; 14];
+        read(&mut sim, Registers::AccelXOutH, &mut buffer);
+        let values: [i16; 7] =
+            core::array::from_fn(|i| i16::from_be_bytes([buffer[2 * i], buffer[2 * i + 1]]));
+        assert_eq!(values, [-2, 300, 16384, -521, 1, -1, 32767]);
+    }
+
+    #[test]
+    fn burst_write_auto_increments() {
+        let mut sim = SimulatedMpu6050::default();
+        // SMPLRT_DIV, CONFIG, GYRO_CONFIG, ACCEL_CONFIG
+        sim.write(
+            ADDRESS,
+            &[Registers::SmprtDiv.get_register_address(), 9, 1, 0x08, 0x10],
+        )
+        .unwrap();
+        let mut buffer = [0u8; 4];
+        read(&mut sim, Registers::SmprtDiv, &mut buffer);
+        assert_eq!(buffer, [9, 1, 0x08, 0x10]);
+    }
+
+    #[test]
+    fn data_registers_update_only_when_awake() {
+        let mut sim = SimulatedMpu6050::default();
+        sim.set_accel_raw(100, 0, 0);
+        assert_eq!(sim.advance_us(1_000_000), 0);
+        assert_eq!(sim.peek(0x3B), 0);
+
+        sim.write(
+            ADDRESS,
+            &[Registers::PowerMgmt1.get_register_address(), 0x01],
+        )
+        .unwrap();
+        // 8 kHz gyro output rate with SMPLRT_DIV = 0
+        assert_eq!(sim.advance_us(1_000), 8);
+        assert_eq!(sim.peek(0x3C), 100);
+    }
+
+    #[test]
+    fn wrong_address_is_not_acknowledged() {
+        let mut sim = SimulatedMpu6050::default();
+        assert_eq!(
+            sim.write(0x69, &[0x6B, 0x00]),
+            Err(SimError::AddressNak(0x69))
+        );
+    }
+}
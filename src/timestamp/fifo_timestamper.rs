@@ -0,0 +1,311 @@
+//! Timestamp reconstruction for FIFO batches
+//!
+//! The FIFO carries no timing information, but frames are written at a fixed rate derived from
+//! the IMU's own oscillator. Each drain gives one observation: the newest frame was written at
+//! most one sample period before the host read FIFO_COUNT. [`FifoTimestamper`] fits a line
+//! through these observations, frame index against host time, which yields a timestamp for
+//! every frame and the actual sample period as seen by the host clock.
+
+use crate::config::DeviceConfig;
+
+/// Default weight given to previous observations at each new batch
+const DEFAULT_FORGETTING_FACTOR: f64 = 0.999;
+
+/// Assigns host clock timestamps to frames drained from the FIFO
+///
+/// Feed every drain to [`Self::add_batch`] in order. The fit assumes no frame was lost, so
+/// call [`Self::reset`] after a FIFO overflow or reset, otherwise the frames after the gap get
+/// timestamps that are too early.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct FifoTimestamper {
+    nominal_period_us: f64,
+    forgetting_factor: f64,
+    /// Frames drained so far, i.e. index of the next frame
+    frames: u64,
+    /// Host time of the first observation, all fitted times are relative to it
+    origin_us: u64,
+    observations: u32,
+    // Exponentially weighted mean and co-moments of (frame index, host time)
+    weight: f64,
+    mean_index: f64,
+    mean_time_us: f64,
+    m_index_index: f64,
+    m_index_time: f64,
+    period_us: f64,
+}
+
+impl FifoTimestamper {
+    /// Create for a nominal sample rate in Hz, as set by DLPF_CFG and SMPLRT_DIV
+    pub fn new(sample_rate_hz: u32) -> Self {
+        Self::with_nominal_period_us(1_000_000.0 / sample_rate_hz.max(1) as f64)
+    }
+
+    const fn with_nominal_period_us(nominal_period_us: f64) -> Self {
+        Self {
+            nominal_period_us,
+            forgetting_factor: DEFAULT_FORGETTING_FACTOR,
+            frames: 0,
+            origin_us: 0,
+            observations: 0,
+            weight: 0.0,
+            mean_index: 0.0,
+            mean_time_us: 0.0,
+            m_index_index: 0.0,
+            m_index_time: 0.0,
+            period_us: nominal_period_us,
+        }
+    }
+
+    /// Create for the sample rate of a device configuration
+    pub fn from_config(config: &DeviceConfig) -> Self {
+        Self::new(config.sample_rate_hz())
+    }
+
+    /// Set how much previous observations are kept at each new batch, between 0 and 1.
+    ///
+    /// Values close to 1 average out more drain jitter, lower values follow clock drift
+    /// caused by temperature changes faster. Default 0.999, i.e. roughly the last thousand
+    /// batches are taken into account.
+    pub fn with_forgetting_factor(mut self, forgetting_factor: f64) -> Self {
+        self.forgetting_factor = forgetting_factor.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Forget all observations, e.g. after the FIFO overflowed or was reset
+    pub fn reset(&mut self) {
+        *self = Self {
+            forgetting_factor: self.forgetting_factor,
+            ..Self::with_nominal_period_us(self.nominal_period_us)
+        };
+    }
+
+    /// Record a drain and get the timestamps of the drained frames.
+    ///
+    /// `host_time_us` is the host time when FIFO_COUNT was read, `available` the number of
+    /// complete frames it reported and `drained` the number actually read, e.g. both from the
+    /// [`FifoDrain`](crate::mpu6050::FifoDrain) returned by `drain_fifo`. Frames left in the
+    /// FIFO are counted again by the next drain.
+    pub fn add_batch(
+        &mut self,
+        host_time_us: u64,
+        available: usize,
+        drained: usize,
+    ) -> BatchTimestamps {
+        let drained = drained.min(available);
+        let first_index = self.frames;
+        if available > 0 {
+            if self.observations == 0 {
+                self.origin_us = host_time_us;
+            }
+            // The newest frame was written on average half a period before the count was read
+            let index = (first_index + available as u64 - 1) as f64;
+            let time_us = host_time_us.wrapping_sub(self.origin_us) as f64 - self.period_us / 2.0;
+            self.observe(index, time_us);
+            self.frames += drained as u64;
+        }
+        BatchTimestamps {
+            timestamper: self.clone(),
+            next_index: first_index,
+            end_index: first_index + drained as u64,
+        }
+    }
+
+    fn observe(&mut self, index: f64, time_us: f64) {
+        self.observations = self.observations.saturating_add(1);
+        self.weight = self.forgetting_factor * self.weight + 1.0;
+        self.m_index_index *= self.forgetting_factor;
+        self.m_index_time *= self.forgetting_factor;
+
+        let d_index = index - self.mean_index;
+        self.mean_index += d_index / self.weight;
+        self.mean_time_us += (time_us - self.mean_time_us) / self.weight;
+        self.m_index_index += d_index * (index - self.mean_index);
+        self.m_index_time += d_index * (time_us - self.mean_time_us);
+
+        if self.observations >= 2 && self.m_index_index > 0.0 {
+            let period_us = self.m_index_time / self.m_index_index;
+            if period_us > 0.0 {
+                self.period_us = period_us;
+            }
+        }
+    }
+
+    /// Host timestamp of the frame with the given index, counted from the first drained frame
+    /// since creation or the last [`Self::reset`]
+    pub fn timestamp_us(&self, frame_index: u64) -> u64 {
+        let offset = self.mean_time_us + (frame_index as f64 - self.mean_index) * self.period_us;
+        let time_us = self.origin_us as f64 + offset;
+        if time_us > 0.0 {
+            time_us as u64
+        } else {
+            0
+        }
+    }
+
+    /// Number of frames drained so far
+    pub const fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Sample period expected from the configuration
+    pub const fn nominal_period_us(&self) -> f64 {
+        self.nominal_period_us
+    }
+
+    /// Sample period measured against the host clock
+    pub const fn period_us(&self) -> f64 {
+        self.period_us
+    }
+
+    /// Drift of the IMU clock relative to the host clock in parts per million.
+    /// Positive when the IMU samples slower than nominal.
+    pub fn drift_ppm(&self) -> f64 {
+        (self.period_us / self.nominal_period_us - 1.0) * 1_000_000.0
+    }
+}
+
+/// Timestamps of the frames of one drain, oldest first
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt-impl", derive(defmt::Format))]
+pub struct BatchTimestamps {
+    timestamper: FifoTimestamper,
+    next_index: u64,
+    end_index: u64,
+}
+
+impl BatchTimestamps {
+    /// Index of the next frame, see [`FifoTimestamper::timestamp_us`]
+    pub const fn next_index(&self) -> u64 {
+        self.next_index
+    }
+}
+
+impl Iterator for BatchTimestamps {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.next_index >= self.end_index {
+            return None;
+        }
+        let timestamp = self.timestamper.timestamp_us(self.next_index);
+        self.next_index += 1;
+        Some(timestamp)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end_index - self.next_index) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for BatchTimestamps {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START_US: f64 = 5_000_300.0;
+    /// IMU clock 50 ppm slower than the nominal 1 kHz
+    const TRUE_PERIOD_US: f64 = 1000.0 * (1.0 + 50e-6);
+
+    /// Frames written by the IMU up to the given host time
+    fn written(host_time_us: u64) -> u64 {
+        ((host_time_us as f64 - START_US) / TRUE_PERIOD_US).floor() as u64 + 1
+    }
+
+    fn truth(frame: u64) -> f64 {
+        START_US + frame as f64 * TRUE_PERIOD_US
+    }
+
+    /// Host times of the drains, every 30 to 40 ms with jitter
+    fn drain_times(count: usize) -> impl Iterator<Item = u64> {
+        let mut seed = 1u32;
+        let mut host_time_us = 5_000_000u64;
+        (0..count).map(move |_| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            host_time_us += 30_000 + (seed >> 16) as u64 % 10_000;
+            host_time_us
+        })
+    }
+
+    #[test]
+    fn steady_batches_track_drift() {
+        let mut timestamper = FifoTimestamper::new(1000);
+        let mut drained = 0;
+        let mut max_error_us = 0.0f64;
+        for host_time_us in drain_times(2000) {
+            let available = (written(host_time_us) - drained) as usize;
+            for (i, timestamp) in timestamper
+                .add_batch(host_time_us, available, available)
+                .enumerate()
+            {
+                let error = timestamp as f64 - truth(drained + i as u64);
+                max_error_us = max_error_us.max(error.abs());
+            }
+            drained += available as u64;
+        }
+        assert_eq!(timestamper.frames(), drained);
+        assert!((timestamper.drift_ppm() - 50.0).abs() < 5.0);
+        assert!(max_error_us < 1000.0, "max error {max_error_us} us");
+    }
+
+    #[test]
+    fn partial_batches_keep_frame_indices() {
+        let mut timestamper = FifoTimestamper::new(1000);
+        let mut drained = 0;
+        let mut last_error_us = 0.0;
+        for host_time_us in drain_times(2000) {
+            // A small frame buffer, the backlog grows and stays in the FIFO
+            let available = (written(host_time_us) - drained) as usize;
+            let batch = timestamper.add_batch(host_time_us, available, 34.min(available));
+            assert_eq!(batch.next_index(), drained);
+            for (i, timestamp) in batch.enumerate() {
+                last_error_us = timestamp as f64 - truth(drained + i as u64);
+            }
+            drained += 34.min(available) as u64;
+        }
+        assert!(written(drain_times(2000).last().unwrap()) > drained + 1000);
+        assert_eq!(timestamper.frames(), drained);
+        assert!((timestamper.drift_ppm() - 50.0).abs() < 5.0);
+        assert!(f64::abs(last_error_us) < 1000.0, "error {last_error_us} us");
+    }
+
+    #[test]
+    fn reset_after_overflow_gap() {
+        let mut timestamper = FifoTimestamper::new(1000);
+        let mut drained = 0;
+        let mut times = drain_times(1000);
+        for host_time_us in times.by_ref().take(500) {
+            let available = (written(host_time_us) - drained) as usize;
+            timestamper.add_batch(host_time_us, available, available);
+            drained += available as u64;
+        }
+
+        // Drains are missed, the FIFO overflows and only keeps the newest 50 frames
+        let before_gap = drained;
+        let host_time_us = times.nth(10).unwrap();
+        let available = 50;
+        let lost = written(host_time_us) - drained - available as u64;
+        let stale = timestamper.clone().add_batch(host_time_us, available, available);
+        let error = stale.last().unwrap() as f64 - truth(drained + lost + available as u64 - 1);
+        assert!(error < -100_000.0, "gap not visible, error {error} us");
+
+        timestamper.reset();
+        drained += lost;
+        let mut max_error_us = 0.0f64;
+        for host_time_us in core::iter::once(host_time_us).chain(times) {
+            let available = (written(host_time_us) - drained) as usize;
+            for (i, timestamp) in timestamper
+                .add_batch(host_time_us, available, available)
+                .enumerate()
+            {
+                let error = timestamp as f64 - truth(drained + i as u64);
+                max_error_us = max_error_us.max(error.abs());
+            }
+            drained += available as u64;
+        }
+        assert_eq!(timestamper.frames(), drained - before_gap - lost);
+        assert!(max_error_us < 1000.0, "max error {max_error_us} us");
+    }
+}
@@ -0,0 +1,3 @@
+
+pub mod fifo_timestamper;
+pub use fifo_timestamper::{BatchTimestamps, FifoTimestamper};